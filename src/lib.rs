@@ -1,4 +1,7 @@
+mod model;
 mod nlp;
+mod nlp_builder;
+mod nlp_validation;
 mod optimizer;
 mod options;
 mod output;
@@ -6,13 +9,31 @@ mod solver;
 mod step_size_control;
 mod vec_utils;
 
-pub use nlp::{dump_nlp, NlpInfo, VariableBounds, NLP};
-pub use optimizer::{Bfgs, SteepestDescent};
-pub use options::Options;
+pub use model::{sum, CompiledModel, Expr, Model, Var};
+pub use nlp::{dump_nlp, Evaluation, EvaluationError, NlpInfo, VariableBounds, NLP};
+pub use nlp_builder::{ClosureNlp, NlpBuilder, NlpBuilderError};
+pub use nlp_validation::{validate_nlp, ConstraintKind, NlpValidationError};
+pub use optimizer::{Bfgs, OptContext, Optimizer, SteepestDescent, StepDirection};
+#[cfg(feature = "serde")]
+pub use options::OptionsError;
 pub use options::{
-    BoundsHandler as OptionsBoundsHandler, ConstraintsHandler as OptionsConstraintsHandler,
-    Logger as OptionsLogger, StepSizeControl as OptionsStepSizeControl,
+    BoundsHandler as OptionsBoundsHandler, BoundsStrategy,
+    ConstraintsHandler as OptionsConstraintsHandler, ConstraintsStrategy,
+    History as OptionsHistory, Logger as OptionsLogger, Optimizer as OptionsOptimizer,
+    StepSizeControl as OptionsStepSizeControl, StepSizeRule, Termination as OptionsTermination,
+};
+pub use options::{InvalidOption, Options, OptionsValidationError};
+#[cfg(feature = "log")]
+pub use output::LogLogger;
+#[cfg(feature = "tracing")]
+pub use output::TracingLogger;
+pub use output::{CsvLogger, Frequency, JsonLinesLogger, SolverLogger, StdoutLogger};
+pub use solver::{
+    AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, CallbackAction, ConvergenceHistory,
+    EvaluationCounts, Iterations, Multipliers, OptimalityMeasures, Solution, Solver,
+    SolverStatistics, TerminationStatus, WarmStart,
+};
+pub use step_size_control::{
+    ArmijoGoldsteinRule, FallbackChain, LineSearchError, MeritFunction, StepInfo, StepSizeControl,
+    StrongWolfeRule,
 };
-pub use output::StdoutLogger;
-pub use solver::{AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, Solver};
-pub use step_size_control::ArmijoGoldsteinRule;
@@ -0,0 +1,157 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Handle to a variable declared in a [`Model`](super::Model).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Var(pub(super) usize);
+
+impl Var {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    pub fn powi(self, n: i32) -> Expr {
+        Expr::from(self).powi(n)
+    }
+
+    pub fn powf(self, p: f64) -> Expr {
+        Expr::from(self).powf(p)
+    }
+
+    pub fn sqrt(self) -> Expr {
+        Expr::from(self).sqrt()
+    }
+
+    pub fn exp(self) -> Expr {
+        Expr::from(self).exp()
+    }
+
+    pub fn ln(self) -> Expr {
+        Expr::from(self).ln()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Expr {
+    Constant(f64),
+    Variable(usize),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+    Powi(Box<Expr>, i32),
+    Powf(Box<Expr>, f64),
+    Exp(Box<Expr>),
+    Ln(Box<Expr>),
+    Sum(Vec<Expr>),
+}
+
+impl Expr {
+    pub fn powi(self, n: i32) -> Expr {
+        Expr::Powi(Box::new(self), n)
+    }
+
+    pub fn powf(self, p: f64) -> Expr {
+        Expr::Powf(Box::new(self), p)
+    }
+
+    pub fn sqrt(self) -> Expr {
+        self.powf(0.5)
+    }
+
+    pub fn exp(self) -> Expr {
+        Expr::Exp(Box::new(self))
+    }
+
+    pub fn ln(self) -> Expr {
+        Expr::Ln(Box::new(self))
+    }
+
+    pub fn eval(&self, xs: &[f64]) -> f64 {
+        match self {
+            Expr::Constant(c) => *c,
+            Expr::Variable(i) => xs[*i],
+            Expr::Add(a, b) => a.eval(xs) + b.eval(xs),
+            Expr::Sub(a, b) => a.eval(xs) - b.eval(xs),
+            Expr::Mul(a, b) => a.eval(xs) * b.eval(xs),
+            Expr::Div(a, b) => a.eval(xs) / b.eval(xs),
+            Expr::Neg(a) => -a.eval(xs),
+            Expr::Powi(a, n) => a.eval(xs).powi(*n),
+            Expr::Powf(a, p) => a.eval(xs).powf(*p),
+            Expr::Exp(a) => a.eval(xs).exp(),
+            Expr::Ln(a) => a.eval(xs).ln(),
+            Expr::Sum(terms) => terms.iter().map(|t| t.eval(xs)).sum(),
+        }
+    }
+}
+
+/// Sums up the given terms, e.g. `sum((0..n).map(|i| x[i] * c[i]))`.
+pub fn sum<I, E>(terms: I) -> Expr
+where
+    I: IntoIterator<Item = E>,
+    E: Into<Expr>,
+{
+    Expr::Sum(terms.into_iter().map(Into::into).collect())
+}
+
+impl From<f64> for Expr {
+    fn from(c: f64) -> Self {
+        Expr::Constant(c)
+    }
+}
+
+impl From<Var> for Expr {
+    fn from(v: Var) -> Self {
+        Expr::Variable(v.0)
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        Expr::Neg(Box::new(self))
+    }
+}
+
+impl Neg for Var {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        -Expr::from(self)
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $variant:ident, $lhs:ty, $rhs:ty) => {
+        impl $trait<$rhs> for $lhs {
+            type Output = Expr;
+
+            fn $method(self, rhs: $rhs) -> Expr {
+                Expr::$variant(Box::new(Expr::from(self)), Box::new(Expr::from(rhs)))
+            }
+        }
+    };
+}
+
+macro_rules! impl_binary_ops {
+    ($($lhs:ty, $rhs:ty);*) => {
+        $(
+            impl_binary_op!(Add, add, Add, $lhs, $rhs);
+            impl_binary_op!(Sub, sub, Sub, $lhs, $rhs);
+            impl_binary_op!(Mul, mul, Mul, $lhs, $rhs);
+            impl_binary_op!(Div, div, Div, $lhs, $rhs);
+        )*
+    };
+}
+
+impl_binary_ops!(
+    Expr, Expr;
+    Expr, Var;
+    Expr, f64;
+    Var, Expr;
+    Var, Var;
+    Var, f64;
+    f64, Expr;
+    f64, Var
+);
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+pub use expr::{sum, Expr, Var};
+use tape::Tape;
+
+use crate::solver::Solution;
+use crate::{NlpInfo, VariableBounds, NLP};
+
+mod expr;
+mod tape;
+
+/// Algebraic description of an optimization problem in terms of named variables.
+///
+/// Objective and constraints are built from [`Expr`]essions; their gradients are derived
+/// automatically when the model is compiled into an [`NLP`].
+#[derive(Default)]
+pub struct Model {
+    names: Vec<String>,
+    indices: HashMap<String, usize>,
+    bounds: Vec<VariableBounds>,
+    initial_guess: Vec<f64>,
+    objective: Option<Expr>,
+    inequality_constraints: Vec<Expr>,
+    equality_constraints: Vec<Expr>,
+}
+
+impl Model {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Declares a variable with bounds `lb <= x <= ub`. Panics if `name` is already taken.
+    pub fn add_variable(&mut self, name: &str, lb: f64, ub: f64, initial_guess: f64) -> Var {
+        assert!(
+            !self.indices.contains_key(name),
+            "variable '{}' is declared twice",
+            name
+        );
+
+        let index = self.names.len();
+
+        self.names.push(name.to_string());
+        self.indices.insert(name.to_string(), index);
+        self.bounds.push(VariableBounds { lb, ub });
+        self.initial_guess.push(initial_guess);
+
+        Var(index)
+    }
+
+    /// Declares the variables `name[0]`, ..., `name[count - 1]` sharing bounds and initial guess.
+    pub fn add_variables(
+        &mut self,
+        name: &str,
+        count: usize,
+        lb: f64,
+        ub: f64,
+        initial_guess: f64,
+    ) -> Vec<Var> {
+        (0..count)
+            .map(|i| self.add_variable(&format!("{}[{}]", name, i), lb, ub, initial_guess))
+            .collect()
+    }
+
+    pub fn variable(&self, name: &str) -> Option<Var> {
+        self.indices.get(name).map(|i| Var(*i))
+    }
+
+    pub fn minimize(&mut self, objective: impl Into<Expr>) {
+        self.objective = Some(objective.into());
+    }
+
+    /// Adds the constraint `g(x) <= 0`.
+    pub fn add_inequality_constraint(&mut self, g: impl Into<Expr>) {
+        self.inequality_constraints.push(g.into());
+    }
+
+    /// Adds the constraint `h(x) = 0`.
+    pub fn add_equality_constraint(&mut self, h: impl Into<Expr>) {
+        self.equality_constraints.push(h.into());
+    }
+
+    pub fn compile(self) -> CompiledModel {
+        let objective = self.objective.unwrap_or(Expr::Constant(0.0));
+
+        CompiledModel {
+            info: NlpInfo {
+                num_variables: self.names.len() as u32,
+                num_inequality_constraints: self.inequality_constraints.len() as u32,
+                num_equality_constraints: self.equality_constraints.len() as u32,
+            },
+            names: self.names,
+            indices: self.indices,
+            bounds: self.bounds,
+            initial_guess: self.initial_guess,
+            objective: Tape::new(&objective),
+            inequality_constraints: self.inequality_constraints.iter().map(Tape::new).collect(),
+            equality_constraints: self.equality_constraints.iter().map(Tape::new).collect(),
+        }
+    }
+}
+
+/// A [`Model`] ready to be handed to the [`Solver`](crate::Solver).
+pub struct CompiledModel {
+    info: NlpInfo,
+    names: Vec<String>,
+    indices: HashMap<String, usize>,
+    bounds: Vec<VariableBounds>,
+    initial_guess: Vec<f64>,
+    objective: Tape,
+    inequality_constraints: Vec<Tape>,
+    equality_constraints: Vec<Tape>,
+}
+
+impl CompiledModel {
+    pub fn variable(&self, name: &str) -> Option<Var> {
+        self.indices.get(name).map(|i| Var(*i))
+    }
+
+    /// Value of the variable `name` in the best solution found; `None` if there is no such
+    /// variable or `solution` has a different number of variables than the model.
+    pub fn value(&self, solution: &Solution, name: &str) -> Option<f64> {
+        if !self.fits(solution) {
+            return None;
+        }
+
+        self.variable(name)
+            .map(|v| solution.best_solution[v.index()])
+    }
+
+    /// All variables of the best solution found, paired with their names in declaration order;
+    /// empty if `solution` has a different number of variables than the model.
+    pub fn named_values<'s>(&'s self, solution: &Solution) -> Vec<(&'s str, f64)> {
+        if !self.fits(solution) {
+            return vec![];
+        }
+
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(solution.best_solution.iter().copied())
+            .collect()
+    }
+
+    fn fits(&self, solution: &Solution) -> bool {
+        solution.best_solution.len() == self.names.len()
+    }
+}
+
+impl NLP for CompiledModel {
+    fn info(&self) -> &NlpInfo {
+        &self.info
+    }
+
+    fn bounds(&self) -> Vec<VariableBounds> {
+        self.bounds.clone()
+    }
+
+    fn objective(&self, xs: &[f64]) -> f64 {
+        self.objective.value(xs)
+    }
+
+    fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+        self.objective.gradient(xs)
+    }
+
+    fn equality_constraints(&self, xs: &[f64]) -> Vec<f64> {
+        self.equality_constraints
+            .iter()
+            .map(|h| h.value(xs))
+            .collect()
+    }
+
+    fn grad_equality_constraints(&self, xs: &[f64]) -> Vec<Vec<f64>> {
+        self.equality_constraints
+            .iter()
+            .map(|h| h.gradient(xs))
+            .collect()
+    }
+
+    fn inequality_constraints(&self, xs: &[f64]) -> Vec<f64> {
+        self.inequality_constraints
+            .iter()
+            .map(|g| g.value(xs))
+            .collect()
+    }
+
+    fn grad_inequality_constraints(&self, xs: &[f64]) -> Vec<Vec<f64>> {
+        self.inequality_constraints
+            .iter()
+            .map(|g| g.gradient(xs))
+            .collect()
+    }
+
+    fn initial_guess(&self) -> Vec<f64> {
+        self.initial_guess.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiled_model_matches_declaration() {
+        let mut model = Model::new();
+
+        let x = model.add_variable("x", 0.0, 1.0, 0.5);
+        let ys = model.add_variables("y", 3, f64::NEG_INFINITY, f64::INFINITY, 2.0);
+
+        model.minimize(x.powi(2) + sum(ys.iter().map(|y| *y * 2.0)));
+        model.add_inequality_constraint(x - ys[0]);
+        model.add_equality_constraint(ys[1] * ys[2] - 1.0);
+
+        assert_eq!(model.variable("y[2]"), Some(ys[2]));
+
+        let nlp = model.compile();
+        let xs = nlp.initial_guess();
+
+        assert_eq!(nlp.info().num_variables, 4);
+        assert_eq!(nlp.info().num_inequality_constraints, 1);
+        assert_eq!(nlp.info().num_equality_constraints, 1);
+        assert_eq!(xs, [0.5, 2.0, 2.0, 2.0]);
+
+        assert_eq!(nlp.objective(&xs), 12.25);
+        assert_eq!(nlp.grad_objective(&xs), [1.0, 2.0, 2.0, 2.0]);
+        assert_eq!(nlp.inequality_constraints(&xs), [-1.5]);
+        assert_eq!(
+            nlp.grad_inequality_constraints(&xs),
+            [[1.0, -1.0, 0.0, 0.0]]
+        );
+        assert_eq!(nlp.equality_constraints(&xs), [3.0]);
+        assert_eq!(nlp.grad_equality_constraints(&xs), [[0.0, 0.0, 2.0, 2.0]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_names_are_rejected() {
+        let mut model = Model::new();
+
+        model.add_variable("x", 0.0, 1.0, 0.5);
+        model.add_variable("x", 0.0, 1.0, 0.5);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::model::Expr;
+
+enum Op {
+    Constant(f64),
+    Variable(usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Div(usize, usize),
+    Neg(usize),
+    Powi(usize, i32),
+    Powf(usize, f64),
+    Exp(usize),
+    Ln(usize),
+    Sum(Vec<usize>),
+}
+
+/// An expression flattened into evaluation order, used for reverse-mode differentiation.
+pub struct Tape {
+    ops: Vec<Op>,
+}
+
+impl Tape {
+    pub fn new(expr: &Expr) -> Self {
+        let mut tape = Tape { ops: vec![] };
+        tape.record(expr);
+        tape
+    }
+
+    fn record(&mut self, expr: &Expr) -> usize {
+        let op = match expr {
+            Expr::Constant(c) => Op::Constant(*c),
+            Expr::Variable(i) => Op::Variable(*i),
+            Expr::Add(a, b) => Op::Add(self.record(a), self.record(b)),
+            Expr::Sub(a, b) => Op::Sub(self.record(a), self.record(b)),
+            Expr::Mul(a, b) => Op::Mul(self.record(a), self.record(b)),
+            Expr::Div(a, b) => Op::Div(self.record(a), self.record(b)),
+            Expr::Neg(a) => Op::Neg(self.record(a)),
+            Expr::Powi(a, n) => Op::Powi(self.record(a), *n),
+            Expr::Powf(a, p) => Op::Powf(self.record(a), *p),
+            Expr::Exp(a) => Op::Exp(self.record(a)),
+            Expr::Ln(a) => Op::Ln(self.record(a)),
+            Expr::Sum(terms) => Op::Sum(terms.iter().map(|t| self.record(t)).collect()),
+        };
+
+        self.ops.push(op);
+        self.ops.len() - 1
+    }
+
+    fn forward(&self, xs: &[f64]) -> Vec<f64> {
+        let mut v: Vec<f64> = Vec::with_capacity(self.ops.len());
+
+        for op in self.ops.iter() {
+            let value = match op {
+                Op::Constant(c) => *c,
+                Op::Variable(i) => xs[*i],
+                Op::Add(a, b) => v[*a] + v[*b],
+                Op::Sub(a, b) => v[*a] - v[*b],
+                Op::Mul(a, b) => v[*a] * v[*b],
+                Op::Div(a, b) => v[*a] / v[*b],
+                Op::Neg(a) => -v[*a],
+                Op::Powi(a, n) => v[*a].powi(*n),
+                Op::Powf(a, p) => v[*a].powf(*p),
+                Op::Exp(a) => v[*a].exp(),
+                Op::Ln(a) => v[*a].ln(),
+                Op::Sum(terms) => terms.iter().map(|t| v[*t]).sum(),
+            };
+
+            v.push(value);
+        }
+
+        v
+    }
+
+    pub fn value(&self, xs: &[f64]) -> f64 {
+        *self.forward(xs).last().unwrap()
+    }
+
+    pub fn gradient(&self, xs: &[f64]) -> Vec<f64> {
+        let v = self.forward(xs);
+        let mut adjoint = vec![0.0; self.ops.len()];
+        let mut grad = vec![0.0; xs.len()];
+
+        *adjoint.last_mut().unwrap() = 1.0;
+
+        for (k, op) in self.ops.iter().enumerate().rev() {
+            let adj = adjoint[k];
+
+            if adj == 0.0 {
+                continue;
+            }
+
+            match op {
+                Op::Constant(_) => {}
+                Op::Variable(i) => grad[*i] += adj,
+                Op::Add(a, b) => {
+                    adjoint[*a] += adj;
+                    adjoint[*b] += adj;
+                }
+                Op::Sub(a, b) => {
+                    adjoint[*a] += adj;
+                    adjoint[*b] -= adj;
+                }
+                Op::Mul(a, b) => {
+                    adjoint[*a] += adj * v[*b];
+                    adjoint[*b] += adj * v[*a];
+                }
+                Op::Div(a, b) => {
+                    adjoint[*a] += adj / v[*b];
+                    adjoint[*b] -= adj * v[*a] / v[*b].powi(2);
+                }
+                Op::Neg(a) => adjoint[*a] -= adj,
+                // x^0 is constant, and 0 * 0^-1 would be NaN at x = 0.
+                Op::Powi(_, 0) => {}
+                Op::Powi(a, n) => adjoint[*a] += adj * f64::from(*n) * v[*a].powi(n - 1),
+                Op::Powf(a, p) => adjoint[*a] += adj * p * v[*a].powf(p - 1.0),
+                Op::Exp(a) => adjoint[*a] += adj * v[k],
+                Op::Ln(a) => adjoint[*a] += adj / v[*a],
+                Op::Sum(terms) => {
+                    for t in terms.iter() {
+                        adjoint[*t] += adj;
+                    }
+                }
+            }
+        }
+
+        grad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{sum, Var};
+
+    fn finite_difference(expr: &Expr, xs: &[f64]) -> Vec<f64> {
+        let h = 1.0E-6;
+
+        (0..xs.len())
+            .map(|i| {
+                let mut xs_plus = xs.to_vec();
+                let mut xs_minus = xs.to_vec();
+                xs_plus[i] += h;
+                xs_minus[i] -= h;
+
+                (expr.eval(&xs_plus) - expr.eval(&xs_minus)) / (2.0 * h)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gradient_matches_finite_differences() {
+        let x = Var(0);
+        let y = Var(1);
+        let z = Var(2);
+
+        let expr = (x * y - 3.0).powi(3) / (1.0 + z.exp()) + (x / y).ln() * z.powf(1.5)
+            - sum(vec![x, y, z]).sqrt()
+            + -(2.0 * x * z);
+
+        let xs = [1.3, 0.7, 2.1];
+        let tape = Tape::new(&expr);
+
+        assert!((tape.value(&xs) - expr.eval(&xs)).abs() < 1.0E-12);
+
+        for (exact, approx) in tape.gradient(&xs).iter().zip(finite_difference(&expr, &xs)) {
+            assert!((exact - approx).abs() < 1.0E-6, "{} vs. {}", exact, approx);
+        }
+    }
+
+    #[test]
+    fn shared_variables_accumulate() {
+        let x = Var(0);
+        let tape = Tape::new(&(x * x * x + x));
+
+        assert_eq!(tape.gradient(&[2.0]), [13.0]);
+    }
+
+    #[test]
+    fn zeroth_power_has_zero_derivative_at_zero() {
+        let x = Var(0);
+        let tape = Tape::new(&(x.powi(0) + x));
+
+        assert_eq!(tape.gradient(&[0.0]), [1.0]);
+    }
+}
@@ -1,5 +1,7 @@
 use core::fmt;
 
+use crate::{ConstraintKind, NlpValidationError};
+
 pub struct NlpInfo {
     pub num_variables: u32,
     pub num_inequality_constraints: u32,
@@ -22,6 +24,64 @@ impl fmt::Display for NlpInfo {
     }
 }
 
+/// Signals that a callback could not be evaluated at the given point, e.g. because it lies
+/// outside the domain of the model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvaluationError {
+    pub message: String,
+    /// Set if a callback returned a result whose length does not match the [`NlpInfo`].
+    pub inconsistency: Option<NlpValidationError>,
+}
+
+impl EvaluationError {
+    pub fn new(message: impl Into<String>) -> Self {
+        EvaluationError {
+            message: message.into(),
+            inconsistency: None,
+        }
+    }
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "evaluation error: {}", self.message)
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+/// Objective and constraint values at a point, plus their derivatives if they were requested.
+///
+/// The buffers are allocated once by [`Evaluation::new`] and overwritten by [`NLP::evaluate`]. The
+/// gradients of the constraints are stored row-major, one row of `num_variables` entries per
+/// constraint.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Evaluation {
+    pub objective: f64,
+    pub inequality_constraints: Vec<f64>,
+    pub equality_constraints: Vec<f64>,
+    pub grad_objective: Vec<f64>,
+    pub grad_inequality_constraints: Vec<f64>,
+    pub grad_equality_constraints: Vec<f64>,
+}
+
+impl Evaluation {
+    pub fn new(info: &NlpInfo) -> Self {
+        let n = info.num_variables as usize;
+        let m_g = info.num_inequality_constraints as usize;
+        let m_h = info.num_equality_constraints as usize;
+
+        Evaluation {
+            objective: 0.0,
+            inequality_constraints: vec![0.0; m_g],
+            equality_constraints: vec![0.0; m_h],
+            grad_objective: vec![0.0; n],
+            grad_inequality_constraints: vec![0.0; m_g * n],
+            grad_equality_constraints: vec![0.0; m_h * n],
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct VariableBounds {
     pub lb: f64,
@@ -55,7 +115,173 @@ pub trait NLP {
         vec![]
     }
 
+    /// Starting point of the solve. NaN entries are omitted; the solver replaces them by the
+    /// midpoint of finite bounds or a point at a distance of `max(1, |bound|)` from a single one.
     fn initial_guess(&self) -> Vec<f64>;
+
+    // The solver only calls the fallible variants below. Override them to report points at which
+    // the model cannot be evaluated; the line search then rejects such trial points.
+
+    fn try_objective(&self, xs: &[f64]) -> Result<f64, EvaluationError> {
+        Ok(self.objective(xs))
+    }
+    fn try_grad_objective(&self, xs: &[f64]) -> Result<Vec<f64>, EvaluationError> {
+        Ok(self.grad_objective(xs))
+    }
+
+    fn try_equality_constraints(&self, xs: &[f64]) -> Result<Vec<f64>, EvaluationError> {
+        Ok(self.equality_constraints(xs))
+    }
+    fn try_grad_equality_constraints(&self, xs: &[f64]) -> Result<Vec<Vec<f64>>, EvaluationError> {
+        Ok(self.grad_equality_constraints(xs))
+    }
+
+    fn try_inequality_constraints(&self, xs: &[f64]) -> Result<Vec<f64>, EvaluationError> {
+        Ok(self.inequality_constraints(xs))
+    }
+    fn try_grad_inequality_constraints(
+        &self,
+        xs: &[f64],
+    ) -> Result<Vec<Vec<f64>>, EvaluationError> {
+        Ok(self.grad_inequality_constraints(xs))
+    }
+
+    // Allocation-free variants writing into buffers of the right size. The defaults copy the
+    // results of the methods above, reporting results of the wrong length as evaluation errors;
+    // override them to avoid the intermediate allocations.
+
+    fn grad_objective_into(&self, xs: &[f64], grad: &mut [f64]) -> Result<(), EvaluationError> {
+        let values = self.try_grad_objective(xs)?;
+
+        if values.len() != grad.len() {
+            return Err(NlpValidationError::GradientLengthMismatch {
+                expected: grad.len(),
+                actual: values.len(),
+            }
+            .into());
+        }
+
+        grad.copy_from_slice(&values);
+        Ok(())
+    }
+
+    fn equality_constraints_into(&self, xs: &[f64], h: &mut [f64]) -> Result<(), EvaluationError> {
+        copy_values(
+            ConstraintKind::Equality,
+            &self.try_equality_constraints(xs)?,
+            h,
+        )
+    }
+    fn grad_equality_constraints_into(
+        &self,
+        xs: &[f64],
+        grad_h: &mut [f64],
+    ) -> Result<(), EvaluationError> {
+        copy_rows(
+            ConstraintKind::Equality,
+            &self.try_grad_equality_constraints(xs)?,
+            self.info().num_equality_constraints as usize,
+            grad_h,
+        )
+    }
+
+    fn inequality_constraints_into(
+        &self,
+        xs: &[f64],
+        g: &mut [f64],
+    ) -> Result<(), EvaluationError> {
+        copy_values(
+            ConstraintKind::Inequality,
+            &self.try_inequality_constraints(xs)?,
+            g,
+        )
+    }
+    fn grad_inequality_constraints_into(
+        &self,
+        xs: &[f64],
+        grad_g: &mut [f64],
+    ) -> Result<(), EvaluationError> {
+        copy_rows(
+            ConstraintKind::Inequality,
+            &self.try_grad_inequality_constraints(xs)?,
+            self.info().num_inequality_constraints as usize,
+            grad_g,
+        )
+    }
+
+    /// Evaluates everything the solver needs at `xs` in one go. Override this if the objective,
+    /// the constraints and possibly their derivatives share expensive intermediate results.
+    fn evaluate(
+        &self,
+        xs: &[f64],
+        with_derivatives: bool,
+        evaluation: &mut Evaluation,
+    ) -> Result<(), EvaluationError> {
+        evaluation.objective = self.try_objective(xs)?;
+        self.inequality_constraints_into(xs, &mut evaluation.inequality_constraints)?;
+        self.equality_constraints_into(xs, &mut evaluation.equality_constraints)?;
+
+        if with_derivatives {
+            self.grad_objective_into(xs, &mut evaluation.grad_objective)?;
+            self.grad_inequality_constraints_into(xs, &mut evaluation.grad_inequality_constraints)?;
+            self.grad_equality_constraints_into(xs, &mut evaluation.grad_equality_constraints)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn copy_values(
+    kind: ConstraintKind,
+    values: &[f64],
+    buffer: &mut [f64],
+) -> Result<(), EvaluationError> {
+    if values.len() != buffer.len() {
+        return Err(NlpValidationError::ConstraintsLengthMismatch {
+            kind,
+            expected: buffer.len(),
+            actual: values.len(),
+        }
+        .into());
+    }
+
+    buffer.copy_from_slice(values);
+    Ok(())
+}
+
+/// Copies the `m` rows of a Jacobian into the row-major buffer `flat`.
+fn copy_rows(
+    kind: ConstraintKind,
+    rows: &[Vec<f64>],
+    m: usize,
+    flat: &mut [f64],
+) -> Result<(), EvaluationError> {
+    if rows.len() != m {
+        return Err(NlpValidationError::JacobianRowsMismatch {
+            kind,
+            expected: m,
+            actual: rows.len(),
+        }
+        .into());
+    }
+
+    let n = flat.len() / m.max(1);
+
+    for (i, row) in rows.iter().enumerate() {
+        if row.len() != n {
+            return Err(NlpValidationError::JacobianRowLengthMismatch {
+                kind,
+                row: i,
+                expected: n,
+                actual: row.len(),
+            }
+            .into());
+        }
+
+        flat[i * n..(i + 1) * n].copy_from_slice(row);
+    }
+
+    Ok(())
 }
 
 pub fn dump_nlp(nlp: &dyn NLP) {
@@ -113,4 +339,68 @@ mod tests {
 
         dump_nlp(&nlp);
     }
+
+    #[test]
+    fn wrong_callback_lengths_are_evaluation_errors() {
+        struct ShortJacobian {
+            info: NlpInfo,
+        }
+
+        impl NLP for ShortJacobian {
+            fn info(&self) -> &NlpInfo {
+                &self.info
+            }
+
+            fn bounds(&self) -> Vec<VariableBounds> {
+                vec![
+                    VariableBounds {
+                        lb: f64::NEG_INFINITY,
+                        ub: f64::INFINITY,
+                    };
+                    2
+                ]
+            }
+
+            fn objective(&self, xs: &[f64]) -> f64 {
+                xs[0] + xs[1]
+            }
+
+            fn grad_objective(&self, _xs: &[f64]) -> Vec<f64> {
+                vec![1.0, 1.0]
+            }
+
+            fn inequality_constraints(&self, xs: &[f64]) -> Vec<f64> {
+                vec![xs[0]]
+            }
+
+            fn grad_inequality_constraints(&self, _xs: &[f64]) -> Vec<Vec<f64>> {
+                vec![vec![1.0]]
+            }
+
+            fn initial_guess(&self) -> Vec<f64> {
+                vec![1.0, 1.0]
+            }
+        }
+
+        let nlp = ShortJacobian {
+            info: NlpInfo {
+                num_variables: 2,
+                num_inequality_constraints: 1,
+                num_equality_constraints: 0,
+            },
+        };
+        let mut evaluation = Evaluation::new(nlp.info());
+
+        assert!(nlp.evaluate(&[1.0, 1.0], false, &mut evaluation).is_ok());
+        assert_eq!(
+            nlp.evaluate(&[1.0, 1.0], true, &mut evaluation),
+            Err(NlpValidationError::JacobianRowLengthMismatch {
+                kind: ConstraintKind::Inequality,
+                row: 0,
+                expected: 2,
+                actual: 1,
+            }
+            .into())
+        );
+    }
 }
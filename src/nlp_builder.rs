@@ -0,0 +1,349 @@
+use core::fmt;
+
+use crate::{NlpInfo, VariableBounds, NLP};
+
+type ScalarFn<'a> = Box<dyn Fn(&[f64]) -> f64 + 'a>;
+type VectorFn<'a> = Box<dyn Fn(&[f64]) -> Vec<f64> + 'a>;
+type MatrixFn<'a> = Box<dyn Fn(&[f64]) -> Vec<Vec<f64>> + 'a>;
+
+enum Constraints<'a> {
+    Single {
+        value: ScalarFn<'a>,
+        grad: VectorFn<'a>,
+    },
+    Block {
+        count: u32,
+        values: VectorFn<'a>,
+        grads: MatrixFn<'a>,
+    },
+}
+
+impl Constraints<'_> {
+    fn count(&self) -> u32 {
+        match self {
+            Constraints::Single { .. } => 1,
+            Constraints::Block { count, .. } => *count,
+        }
+    }
+
+    fn append_values(&self, xs: &[f64], values: &mut Vec<f64>) {
+        match self {
+            Constraints::Single { value, .. } => values.push(value(xs)),
+            Constraints::Block { values: f, .. } => values.extend(f(xs)),
+        }
+    }
+
+    fn append_grads(&self, xs: &[f64], grads: &mut Vec<Vec<f64>>) {
+        match self {
+            Constraints::Single { grad, .. } => grads.push(grad(xs)),
+            Constraints::Block { grads: f, .. } => grads.extend(f(xs)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NlpBuilderError {
+    MissingObjective,
+    MissingGradient,
+    MissingInitialGuess,
+    BoundsLengthMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for NlpBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NlpBuilderError::MissingObjective => write!(f, "no objective function given"),
+            NlpBuilderError::MissingGradient => write!(f, "no objective gradient given"),
+            NlpBuilderError::MissingInitialGuess => write!(f, "no initial guess given"),
+            NlpBuilderError::BoundsLengthMismatch { expected, actual } => write!(
+                f,
+                "got bounds for {} variables, but the initial guess has {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NlpBuilderError {}
+
+/// Assembles an [`NLP`] from closures instead of a hand-written `impl NLP`.
+///
+/// The number of variables is taken from the initial guess and the constraint counts from the
+/// constraints that were added. Variables without explicit bounds are unbounded.
+#[derive(Default)]
+pub struct NlpBuilder<'a> {
+    objective: Option<ScalarFn<'a>>,
+    grad_objective: Option<VectorFn<'a>>,
+    bounds: Option<Vec<VariableBounds>>,
+    initial_guess: Option<Vec<f64>>,
+    inequality_constraints: Vec<Constraints<'a>>,
+    equality_constraints: Vec<Constraints<'a>>,
+}
+
+impl<'a> NlpBuilder<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn objective(mut self, f: impl Fn(&[f64]) -> f64 + 'a) -> Self {
+        self.objective = Some(Box::new(f));
+        self
+    }
+
+    pub fn grad_objective(mut self, grad_f: impl Fn(&[f64]) -> Vec<f64> + 'a) -> Self {
+        self.grad_objective = Some(Box::new(grad_f));
+        self
+    }
+
+    pub fn bounds(mut self, bounds: Vec<VariableBounds>) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    pub fn initial_guess(mut self, initial_guess: Vec<f64>) -> Self {
+        self.initial_guess = Some(initial_guess);
+        self
+    }
+
+    /// Adds a single constraint `g(x) <= 0`.
+    pub fn inequality_constraint(
+        mut self,
+        g: impl Fn(&[f64]) -> f64 + 'a,
+        grad_g: impl Fn(&[f64]) -> Vec<f64> + 'a,
+    ) -> Self {
+        self.inequality_constraints.push(Constraints::Single {
+            value: Box::new(g),
+            grad: Box::new(grad_g),
+        });
+        self
+    }
+
+    /// Adds `count` constraints `g(x) <= 0` evaluated together; `grad_g` returns one gradient per
+    /// constraint.
+    pub fn inequality_constraints(
+        mut self,
+        count: u32,
+        g: impl Fn(&[f64]) -> Vec<f64> + 'a,
+        grad_g: impl Fn(&[f64]) -> Vec<Vec<f64>> + 'a,
+    ) -> Self {
+        self.inequality_constraints.push(Constraints::Block {
+            count,
+            values: Box::new(g),
+            grads: Box::new(grad_g),
+        });
+        self
+    }
+
+    /// Adds a single constraint `h(x) = 0`.
+    pub fn equality_constraint(
+        mut self,
+        h: impl Fn(&[f64]) -> f64 + 'a,
+        grad_h: impl Fn(&[f64]) -> Vec<f64> + 'a,
+    ) -> Self {
+        self.equality_constraints.push(Constraints::Single {
+            value: Box::new(h),
+            grad: Box::new(grad_h),
+        });
+        self
+    }
+
+    /// Adds `count` constraints `h(x) = 0` evaluated together; `grad_h` returns one gradient per
+    /// constraint.
+    pub fn equality_constraints(
+        mut self,
+        count: u32,
+        h: impl Fn(&[f64]) -> Vec<f64> + 'a,
+        grad_h: impl Fn(&[f64]) -> Vec<Vec<f64>> + 'a,
+    ) -> Self {
+        self.equality_constraints.push(Constraints::Block {
+            count,
+            values: Box::new(h),
+            grads: Box::new(grad_h),
+        });
+        self
+    }
+
+    pub fn build(self) -> Result<ClosureNlp<'a>, NlpBuilderError> {
+        let objective = self.objective.ok_or(NlpBuilderError::MissingObjective)?;
+        let grad_objective = self
+            .grad_objective
+            .ok_or(NlpBuilderError::MissingGradient)?;
+        let initial_guess = self
+            .initial_guess
+            .ok_or(NlpBuilderError::MissingInitialGuess)?;
+
+        let num_variables = initial_guess.len();
+
+        let bounds = match self.bounds {
+            Some(bounds) if bounds.len() != num_variables => {
+                return Err(NlpBuilderError::BoundsLengthMismatch {
+                    expected: num_variables,
+                    actual: bounds.len(),
+                })
+            }
+            Some(bounds) => bounds,
+            None => vec![
+                VariableBounds {
+                    lb: f64::NEG_INFINITY,
+                    ub: f64::INFINITY,
+                };
+                num_variables
+            ],
+        };
+
+        Ok(ClosureNlp {
+            info: NlpInfo {
+                num_variables: num_variables as u32,
+                num_inequality_constraints: self
+                    .inequality_constraints
+                    .iter()
+                    .map(Constraints::count)
+                    .sum(),
+                num_equality_constraints: self
+                    .equality_constraints
+                    .iter()
+                    .map(Constraints::count)
+                    .sum(),
+            },
+            bounds,
+            initial_guess,
+            objective,
+            grad_objective,
+            inequality_constraints: self.inequality_constraints,
+            equality_constraints: self.equality_constraints,
+        })
+    }
+}
+
+/// The [`NLP`] produced by [`NlpBuilder::build`].
+pub struct ClosureNlp<'a> {
+    info: NlpInfo,
+    bounds: Vec<VariableBounds>,
+    initial_guess: Vec<f64>,
+    objective: ScalarFn<'a>,
+    grad_objective: VectorFn<'a>,
+    inequality_constraints: Vec<Constraints<'a>>,
+    equality_constraints: Vec<Constraints<'a>>,
+}
+
+impl NLP for ClosureNlp<'_> {
+    fn info(&self) -> &NlpInfo {
+        &self.info
+    }
+
+    fn bounds(&self) -> Vec<VariableBounds> {
+        self.bounds.clone()
+    }
+
+    fn objective(&self, xs: &[f64]) -> f64 {
+        (self.objective)(xs)
+    }
+
+    fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+        (self.grad_objective)(xs)
+    }
+
+    fn equality_constraints(&self, xs: &[f64]) -> Vec<f64> {
+        let mut h = Vec::with_capacity(self.info.num_equality_constraints as usize);
+
+        for constraints in self.equality_constraints.iter() {
+            constraints.append_values(xs, &mut h);
+        }
+
+        h
+    }
+
+    fn grad_equality_constraints(&self, xs: &[f64]) -> Vec<Vec<f64>> {
+        let mut grad_h = Vec::with_capacity(self.info.num_equality_constraints as usize);
+
+        for constraints in self.equality_constraints.iter() {
+            constraints.append_grads(xs, &mut grad_h);
+        }
+
+        grad_h
+    }
+
+    fn inequality_constraints(&self, xs: &[f64]) -> Vec<f64> {
+        let mut g = Vec::with_capacity(self.info.num_inequality_constraints as usize);
+
+        for constraints in self.inequality_constraints.iter() {
+            constraints.append_values(xs, &mut g);
+        }
+
+        g
+    }
+
+    fn grad_inequality_constraints(&self, xs: &[f64]) -> Vec<Vec<f64>> {
+        let mut grad_g = Vec::with_capacity(self.info.num_inequality_constraints as usize);
+
+        for constraints in self.inequality_constraints.iter() {
+            constraints.append_grads(xs, &mut grad_g);
+        }
+
+        grad_g
+    }
+
+    fn initial_guess(&self) -> Vec<f64> {
+        self.initial_guess.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_are_inferred() {
+        let nlp = NlpBuilder::new()
+            .objective(|xs| xs[0].powi(2) + xs[1].powi(2))
+            .grad_objective(|xs| vec![2.0 * xs[0], 2.0 * xs[1]])
+            .initial_guess(vec![1.0, 2.0])
+            .inequality_constraint(|xs| xs[0] - 1.0, |_| vec![1.0, 0.0])
+            .inequality_constraints(
+                2,
+                |xs| vec![xs[1] - 1.0, -xs[1]],
+                |_| vec![vec![0.0, 1.0], vec![0.0, -1.0]],
+            )
+            .equality_constraint(|xs| xs[0] + xs[1] - 1.0, |_| vec![1.0, 1.0])
+            .build()
+            .unwrap();
+
+        assert_eq!(nlp.info().num_variables, 2);
+        assert_eq!(nlp.info().num_inequality_constraints, 3);
+        assert_eq!(nlp.info().num_equality_constraints, 1);
+
+        assert_eq!(nlp.inequality_constraints(&[1.0, 2.0]), [0.0, 1.0, -2.0]);
+        assert_eq!(
+            nlp.grad_inequality_constraints(&[1.0, 2.0]),
+            [vec![1.0, 0.0], vec![0.0, 1.0], vec![0.0, -1.0]]
+        );
+        assert_eq!(nlp.equality_constraints(&[1.0, 2.0]), [2.0]);
+
+        for bounds in nlp.bounds() {
+            assert_eq!(bounds.lb, f64::NEG_INFINITY);
+            assert_eq!(bounds.ub, f64::INFINITY);
+        }
+    }
+
+    #[test]
+    fn missing_parts_are_reported() {
+        assert_eq!(
+            NlpBuilder::new().build().err(),
+            Some(NlpBuilderError::MissingObjective)
+        );
+
+        assert_eq!(
+            NlpBuilder::new()
+                .objective(|xs| xs[0])
+                .grad_objective(|_| vec![1.0])
+                .initial_guess(vec![0.0])
+                .bounds(vec![VariableBounds { lb: 0.0, ub: 1.0 }; 2])
+                .build()
+                .err(),
+            Some(NlpBuilderError::BoundsLengthMismatch {
+                expected: 1,
+                actual: 2
+            })
+        );
+    }
+}
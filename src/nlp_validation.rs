@@ -0,0 +1,340 @@
+use core::fmt;
+
+use crate::{Evaluation, EvaluationError, NLP};
+
+/// The constraints an [`NlpValidationError`] refers to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConstraintKind {
+    Inequality,
+    Equality,
+}
+
+impl fmt::Display for ConstraintKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstraintKind::Inequality => write!(f, "inequality"),
+            ConstraintKind::Equality => write!(f, "equality"),
+        }
+    }
+}
+
+/// An inconsistency between the [`NlpInfo`](crate::NlpInfo) of an NLP and its callbacks.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NlpValidationError {
+    BoundsLengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    InvertedBounds {
+        variable: usize,
+        lb: f64,
+        ub: f64,
+    },
+    InitialGuessLengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// The initial guess lies on or outside the bounds, where the barrier is infinite.
+    InitialGuessNotInterior {
+        variable: usize,
+        value: f64,
+        lb: f64,
+        ub: f64,
+    },
+    GradientLengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    ConstraintsLengthMismatch {
+        kind: ConstraintKind,
+        expected: usize,
+        actual: usize,
+    },
+    JacobianRowsMismatch {
+        kind: ConstraintKind,
+        expected: usize,
+        actual: usize,
+    },
+    JacobianRowLengthMismatch {
+        kind: ConstraintKind,
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// An entry of a [`WarmStart`](crate::WarmStart), e.g. `inequality_multipliers`, whose
+    /// length does not match the NLP.
+    WarmStartLengthMismatch {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for NlpValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NlpValidationError::BoundsLengthMismatch { expected, actual } => write!(
+                f,
+                "got bounds for {} variables, but the NLP has {}",
+                actual, expected
+            ),
+            NlpValidationError::InvertedBounds { variable, lb, ub } => write!(
+                f,
+                "lower bound {} of variable {} exceeds its upper bound {}",
+                lb, variable, ub
+            ),
+            NlpValidationError::InitialGuessLengthMismatch { expected, actual } => write!(
+                f,
+                "the initial guess has {} entries, but the NLP has {} variables",
+                actual, expected
+            ),
+            NlpValidationError::InitialGuessNotInterior {
+                variable,
+                value,
+                lb,
+                ub,
+            } => write!(
+                f,
+                "initial guess {} of variable {} is not strictly within its bounds ({}, {})",
+                value, variable, lb, ub
+            ),
+            NlpValidationError::GradientLengthMismatch { expected, actual } => write!(
+                f,
+                "the objective gradient has {} entries, but the NLP has {} variables",
+                actual, expected
+            ),
+            NlpValidationError::ConstraintsLengthMismatch {
+                kind,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "got {} {} constraint values, but the NLP has {} {} constraints",
+                actual, kind, expected, kind
+            ),
+            NlpValidationError::JacobianRowsMismatch {
+                kind,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "the {} constraint Jacobian has {} rows, but the NLP has {} {} constraints",
+                kind, actual, expected, kind
+            ),
+            NlpValidationError::JacobianRowLengthMismatch {
+                kind,
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "row {} of the {} constraint Jacobian has {} entries, but the NLP has {} variables",
+                row, kind, actual, expected
+            ),
+            NlpValidationError::WarmStartLengthMismatch {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "the warm start has {} entries in {}, but the NLP needs {}",
+                actual, field, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NlpValidationError {}
+
+impl From<NlpValidationError> for EvaluationError {
+    fn from(error: NlpValidationError) -> Self {
+        EvaluationError {
+            message: error.to_string(),
+            inconsistency: Some(error),
+        }
+    }
+}
+
+/// Checks that the bounds, the initial guess and the constraints of `nlp` match its
+/// [`NlpInfo`](crate::NlpInfo), and that the initial guess lies strictly within the bounds.
+///
+/// Omitted (NaN) entries of the initial guess are not checked against the bounds, see
+/// [`NLP::initial_guess`]. The lengths of the constraints and their Jacobians are checked by
+/// evaluating the NLP at the initial guess with [`NLP::evaluate`]; if it cannot be evaluated
+/// there, they are not checked.
+pub fn validate_nlp<N: NLP + ?Sized>(nlp: &N) -> Result<(), NlpValidationError> {
+    let initial_guess = nlp.initial_guess();
+    validate_nlp_at(nlp, &initial_guess)?;
+
+    match nlp.evaluate(&initial_guess, true, &mut Evaluation::new(nlp.info())) {
+        Err(EvaluationError {
+            inconsistency: Some(error),
+            ..
+        }) => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// The checks of [`validate_nlp`] that need no evaluation, with `xs` in place of the initial
+/// guess.
+pub(crate) fn validate_nlp_at<N: NLP + ?Sized>(
+    nlp: &N,
+    xs: &[f64],
+) -> Result<(), NlpValidationError> {
+    let n = nlp.info().num_variables as usize;
+    let bounds = nlp.bounds();
+
+    if bounds.len() != n {
+        return Err(NlpValidationError::BoundsLengthMismatch {
+            expected: n,
+            actual: bounds.len(),
+        });
+    }
+
+    if let Some((variable, b)) = bounds.iter().enumerate().find(|(_, b)| b.lb > b.ub) {
+        return Err(NlpValidationError::InvertedBounds {
+            variable,
+            lb: b.lb,
+            ub: b.ub,
+        });
+    }
+
+    if xs.len() != n {
+        return Err(NlpValidationError::InitialGuessLengthMismatch {
+            expected: n,
+            actual: xs.len(),
+        });
+    }
+
+    if let Some((variable, (x, b))) = xs
+        .iter()
+        .zip(bounds.iter())
+        .enumerate()
+        .find(|(_, (x, b))| !(x.is_nan() || **x > b.lb && **x < b.ub))
+    {
+        return Err(NlpValidationError::InitialGuessNotInterior {
+            variable,
+            value: *x,
+            lb: b.lb,
+            ub: b.ub,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NlpInfo, VariableBounds};
+
+    struct Sloppy {
+        info: NlpInfo,
+        bounds: Vec<VariableBounds>,
+        initial_guess: Vec<f64>,
+        jacobian: Vec<Vec<f64>>,
+    }
+
+    impl NLP for Sloppy {
+        fn info(&self) -> &NlpInfo {
+            &self.info
+        }
+
+        fn bounds(&self) -> Vec<VariableBounds> {
+            self.bounds.clone()
+        }
+
+        fn objective(&self, xs: &[f64]) -> f64 {
+            xs.iter().map(|x| x * x).sum()
+        }
+
+        fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+            xs.iter().map(|x| 2.0 * x).collect()
+        }
+
+        fn inequality_constraints(&self, xs: &[f64]) -> Vec<f64> {
+            vec![xs[0] - 1.0]
+        }
+
+        fn grad_inequality_constraints(&self, _xs: &[f64]) -> Vec<Vec<f64>> {
+            self.jacobian.clone()
+        }
+
+        fn initial_guess(&self) -> Vec<f64> {
+            self.initial_guess.clone()
+        }
+    }
+
+    fn sloppy() -> Sloppy {
+        Sloppy {
+            info: NlpInfo {
+                num_variables: 2,
+                num_inequality_constraints: 1,
+                num_equality_constraints: 0,
+            },
+            bounds: vec![
+                VariableBounds {
+                    lb: 0.0,
+                    ub: f64::INFINITY,
+                };
+                2
+            ],
+            initial_guess: vec![1.0, 1.0],
+            jacobian: vec![vec![1.0, 0.0]],
+        }
+    }
+
+    #[test]
+    fn consistent_nlp_is_valid() {
+        assert_eq!(validate_nlp(&sloppy()), Ok(()));
+    }
+
+    #[test]
+    fn offending_component_is_reported() {
+        let mut nlp = sloppy();
+        nlp.bounds[1] = VariableBounds { lb: 2.0, ub: 1.0 };
+        assert_eq!(
+            validate_nlp(&nlp),
+            Err(NlpValidationError::InvertedBounds {
+                variable: 1,
+                lb: 2.0,
+                ub: 1.0
+            })
+        );
+
+        let mut nlp = sloppy();
+        nlp.initial_guess[0] = 0.0;
+        assert_eq!(
+            validate_nlp(&nlp),
+            Err(NlpValidationError::InitialGuessNotInterior {
+                variable: 0,
+                value: 0.0,
+                lb: 0.0,
+                ub: f64::INFINITY
+            })
+        );
+
+        let mut nlp = sloppy();
+        nlp.info.num_equality_constraints = 1;
+        assert_eq!(
+            validate_nlp(&nlp),
+            Err(NlpValidationError::ConstraintsLengthMismatch {
+                kind: ConstraintKind::Equality,
+                expected: 1,
+                actual: 0
+            })
+        );
+
+        let mut nlp = sloppy();
+        nlp.jacobian[0].pop();
+        assert_eq!(
+            validate_nlp(&nlp),
+            Err(NlpValidationError::JacobianRowLengthMismatch {
+                kind: ConstraintKind::Inequality,
+                row: 0,
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+}
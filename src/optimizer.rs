@@ -1,7 +1,10 @@
-use crate::{vec_utils, AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, NLP};
+use crate::{AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, OptimalityMeasures, NLP};
 use nalgebra::{DMatrix, DVector};
+use std::time::Duration;
 
-#[allow(dead_code)]
+/// State of the solve that optimizers work on and loggers and callbacks observe.
+#[derive(Clone)]
+#[non_exhaustive]
 pub struct OptContext {
     pub iteration: u32,
     pub x_current: Vec<f64>,
@@ -11,50 +14,112 @@ pub struct OptContext {
     pub pure_objective: f64,
     pub objective_grad: Vec<f64>,
     pub direction_scale_factor: f64,
+    pub optimality: OptimalityMeasures,
+    pub barrier_parameter: f64,
+    pub penalty_parameter: f64,
+    /// Wall-clock time since the solve started.
+    pub elapsed: Duration,
+}
+
+impl OptContext {
+    /// The context before the first iteration, starting from the initial guess of `nlp`.
+    pub fn new<N: NLP + ?Sized>(
+        nlp: &N,
+        bounds_handler: &BarrierBoundsHandler,
+        constraints_handler: &AugmentedLagrangianConstraintHandler,
+    ) -> Self {
+        OptContext {
+            iteration: 0,
+            x_current: nlp.initial_guess(),
+            x_previous: nlp.initial_guess(),
+            objective_current: f64::INFINITY,
+            objective_previous: f64::INFINITY,
+            pure_objective: f64::INFINITY,
+            objective_grad: vec![f64::INFINITY; nlp.info().num_variables as usize],
+            direction_scale_factor: 1.0,
+            optimality: Default::default(),
+            barrier_parameter: bounds_handler.barrier_parameter,
+            penalty_parameter: constraints_handler.c,
+            elapsed: Duration::from_secs(0),
+        }
+    }
 }
 
 pub type StepDirection = Vec<f64>;
 
+/// Computes search directions; the solver does the line search and the handling of bounds and
+/// constraints.
 pub trait Optimizer<Nlp: NLP> {
+    /// Resets the optimizer for a new solve and returns the initial context.
     fn initialize(
         &mut self,
         nlp: &Nlp,
         bounds_handler: &BarrierBoundsHandler,
         constraint_handler: &AugmentedLagrangianConstraintHandler,
     ) -> OptContext;
-    fn iterate(&mut self, nlp: &Nlp, context: &mut OptContext) -> StepDirection;
-    fn done(&self, context: &OptContext) -> bool;
-}
 
-pub struct SteepestDescent {}
+    /// Writes the next search direction into `direction`, given the gradient of the penalized
+    /// objective at the current iterate in `context`. `direction_scale_factor` holds the step
+    /// length of the previous iteration.
+    fn iterate(&mut self, nlp: &Nlp, context: &mut OptContext, direction: &mut StepDirection);
 
-impl<Nlp: NLP> Optimizer<Nlp> for SteepestDescent {
+    /// The current approximation of the inverse Hessian, row by row, if the optimizer keeps one.
+    fn inverse_hessian(&self) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// Replaces the initial approximation of the inverse Hessian; called after `initialize`.
+    fn set_inverse_hessian(&mut self, _inverse_hessian: &[f64]) {}
+
+    /// How often the optimizer discarded its curvature information since `initialize`.
+    fn num_resets(&self) -> u32 {
+        0
+    }
+}
+
+impl<Nlp: NLP, O: Optimizer<Nlp> + ?Sized> Optimizer<Nlp> for Box<O> {
     fn initialize(
         &mut self,
         nlp: &Nlp,
-        _bounds_handler: &BarrierBoundsHandler,
-        _constraint_handler: &AugmentedLagrangianConstraintHandler,
+        bounds_handler: &BarrierBoundsHandler,
+        constraint_handler: &AugmentedLagrangianConstraintHandler,
     ) -> OptContext {
-        let nlp_info = nlp.info();
+        (**self).initialize(nlp, bounds_handler, constraint_handler)
+    }
 
-        OptContext {
-            iteration: 0,
-            x_current: nlp.initial_guess(),
-            x_previous: nlp.initial_guess(),
-            objective_current: f64::INFINITY,
-            objective_previous: f64::INFINITY,
-            pure_objective: f64::INFINITY,
-            objective_grad: vec![f64::INFINITY; nlp_info.num_variables as usize],
-            direction_scale_factor: 1.0,
-        }
+    fn iterate(&mut self, nlp: &Nlp, context: &mut OptContext, direction: &mut StepDirection) {
+        (**self).iterate(nlp, context, direction)
     }
 
-    fn iterate(&mut self, _nlp: &Nlp, context: &mut OptContext) -> StepDirection {
-        vec_utils::scaled(&context.objective_grad, -1.0)
+    fn inverse_hessian(&self) -> Option<Vec<f64>> {
+        (**self).inverse_hessian()
     }
 
-    fn done(&self, context: &OptContext) -> bool {
-        (context.objective_current - context.objective_previous).abs() < 1.0E-9
+    fn set_inverse_hessian(&mut self, inverse_hessian: &[f64]) {
+        (**self).set_inverse_hessian(inverse_hessian)
+    }
+
+    fn num_resets(&self) -> u32 {
+        (**self).num_resets()
+    }
+}
+
+pub struct SteepestDescent {}
+
+impl<Nlp: NLP> Optimizer<Nlp> for SteepestDescent {
+    fn initialize(
+        &mut self,
+        nlp: &Nlp,
+        bounds_handler: &BarrierBoundsHandler,
+        constraint_handler: &AugmentedLagrangianConstraintHandler,
+    ) -> OptContext {
+        OptContext::new(nlp, bounds_handler, constraint_handler)
+    }
+
+    fn iterate(&mut self, _nlp: &Nlp, context: &mut OptContext, direction: &mut StepDirection) {
+        for (d_i, grad_i) in direction.iter_mut().zip(context.objective_grad.iter()) {
+            *d_i = -grad_i;
+        }
     }
 }
 
@@ -62,10 +127,16 @@ impl<Nlp: NLP> Optimizer<Nlp> for SteepestDescent {
 pub struct Bfgs {
     g_k: DVector<f64>,
     d_k: DVector<f64>,
+    q_k: DVector<f64>,
+    p_k: DVector<f64>,
+    H_q_k: DVector<f64>,
     H: DMatrix<f64>,
-    n: u32,
+    resets: u32,
 }
 
+/// Smallest cosine between step and gradient change for which [`Bfgs`] updates its approximation.
+const MIN_CURVATURE: f64 = 1.0E-8;
+
 impl Bfgs {
     #[allow(non_snake_case, dead_code)]
     pub fn new(nlp: &impl NLP) -> Self {
@@ -73,10 +144,13 @@ impl Bfgs {
         let H = DMatrix::<f64>::identity(n as usize, n as usize);
 
         Bfgs {
-            g_k: DVector::<f64>::zeros(0),
-            d_k: DVector::<f64>::zeros(0),
+            g_k: DVector::<f64>::zeros(n as usize),
+            d_k: DVector::<f64>::zeros(n as usize),
+            q_k: DVector::<f64>::zeros(n as usize),
+            p_k: DVector::<f64>::zeros(n as usize),
+            H_q_k: DVector::<f64>::zeros(n as usize),
             H,
-            n,
+            resets: 0,
         }
     }
 }
@@ -88,81 +162,82 @@ impl<Nlp: NLP> Optimizer<Nlp> for Bfgs {
         bounds_handler: &BarrierBoundsHandler,
         constraint_handler: &AugmentedLagrangianConstraintHandler,
     ) -> OptContext {
-        let initial_point = nlp.initial_guess();
-
-        let g = nlp.inequality_constraints(&initial_point);
-        let h = nlp.equality_constraints(&initial_point);
-        let grad_f = bounds_handler
-            .adapted_objective_gradient(&initial_point, &nlp.grad_objective(&initial_point));
-
-        let objective_grad = constraint_handler.adapted_objective_grad(
-            &grad_f,
-            &g,
-            &nlp.grad_inequality_constraints(&initial_point)
-                .iter()
-                .map(|x| &x[..])
-                .collect::<Vec<_>>(),
-            &h,
-            &nlp.grad_equality_constraints(&initial_point)
-                .iter()
-                .map(|x| &x[..])
-                .collect::<Vec<_>>(),
-        );
-
-        self.g_k = DVector::<f64>::from_vec(objective_grad);
-        self.d_k = -1.0 * &self.g_k;
+        self.H.fill_with_identity();
+        self.resets = 0;
 
-        OptContext {
-            iteration: 0,
-            x_current: nlp.initial_guess(),
-            x_previous: nlp.initial_guess(),
-            objective_current: f64::INFINITY,
-            objective_previous: f64::INFINITY,
-            pure_objective: f64::INFINITY,
-            objective_grad: self.g_k.as_slice().to_vec(),
-            direction_scale_factor: 1.0,
-        }
+        OptContext::new(nlp, bounds_handler, constraint_handler)
     }
 
+    // all updates work in place on buffers allocated in `new`
     #[allow(non_snake_case)]
-    fn iterate(&mut self, _nlp: &Nlp, context: &mut OptContext) -> StepDirection {
+    fn iterate(&mut self, _nlp: &Nlp, context: &mut OptContext, direction: &mut StepDirection) {
         if context.iteration == 1 {
-            return self.d_k.as_slice().to_vec();
+            self.g_k.copy_from_slice(&context.objective_grad);
+            self.d_k.sygemv(-1.0, &self.H, &self.g_k, 0.);
+
+            direction.copy_from_slice(self.d_k.as_slice());
+            return;
         }
 
-        let g_k_next = DVector::<f64>::from_vec(context.objective_grad.to_vec());
-        let q_k = &g_k_next - &self.g_k;
-        let p_k = context.direction_scale_factor * &self.d_k;
+        self.q_k.copy_from_slice(&context.objective_grad);
+        self.q_k -= &self.g_k;
 
-        self.g_k = g_k_next;
+        self.p_k.copy_from(&self.d_k);
+        self.p_k *= context.direction_scale_factor;
 
-        let mut H_q_k = DVector::<f64>::zeros(self.n as usize);
-        H_q_k.sygemv(1.0, &self.H, &q_k, 0.);
+        self.g_k.copy_from_slice(&context.objective_grad);
 
-        let p_k_q_k = p_k.dot(&q_k);
+        let (q_k, p_k, H_q_k) = (&self.q_k, &self.p_k, &mut self.H_q_k);
 
-        self.H.syger(
-            1. / p_k_q_k + q_k.dot(&H_q_k) / p_k_q_k.powi(2),
-            &p_k,
-            &p_k,
-            1.,
-        );
-        self.H.ger(-1. / p_k_q_k, &p_k, &H_q_k, 1.);
-        self.H.ger(-1. / p_k_q_k, &H_q_k, &p_k, 1.);
+        H_q_k.sygemv(1.0, &self.H, q_k, 0.);
+
+        let p_k_q_k = p_k.dot(q_k);
+
+        // without sufficiently positive curvature along the step, the update would lose positive
+        // definiteness or divide by (almost) zero; start over from the identity instead
+        if p_k_q_k > MIN_CURVATURE * p_k.norm() * q_k.norm() {
+            self.H.syger(
+                1. / p_k_q_k + q_k.dot(H_q_k) / p_k_q_k.powi(2),
+                p_k,
+                p_k,
+                1.,
+            );
+            self.H.ger(-1. / p_k_q_k, p_k, H_q_k, 1.);
+            self.H.ger(-1. / p_k_q_k, H_q_k, p_k, 1.);
+        } else {
+            self.H.fill_with_identity();
+            self.resets += 1;
+        }
 
         self.d_k.sygemv(-1.0, &self.H, &self.g_k, 0.);
 
-        self.d_k.as_slice().to_vec()
+        direction.copy_from_slice(self.d_k.as_slice());
+    }
+
+    fn inverse_hessian(&self) -> Option<Vec<f64>> {
+        // the rank-one updates only maintain the lower triangle
+        let mut inverse_hessian = self.H.clone();
+        inverse_hessian.fill_upper_triangle_with_lower_triangle();
+
+        Some(inverse_hessian.as_slice().to_vec())
     }
 
-    fn done(&self, context: &OptContext) -> bool {
-        (context.objective_current - context.objective_previous).abs() < 1.0E-12
+    fn set_inverse_hessian(&mut self, inverse_hessian: &[f64]) {
+        self.H.copy_from_slice(inverse_hessian);
+    }
+
+    fn num_resets(&self) -> u32 {
+        self.resets
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::optimizer::OptContext;
+    use crate::optimizer::{Bfgs, OptContext, Optimizer};
+    use crate::{
+        AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, ClosureNlp, NlpBuilder, NLP,
+    };
+    use std::time::Duration;
 
     #[test]
     fn opt_context_can_be_modified() {
@@ -179,6 +254,10 @@ mod tests {
             pure_objective: 0.0,
             objective_grad: vec![4.4, 5.5, 6.6],
             direction_scale_factor: 1.0,
+            optimality: Default::default(),
+            barrier_parameter: 1.0E-6,
+            penalty_parameter: 1.0E9,
+            elapsed: Duration::from_secs(0),
         };
 
         assert_eq!(oc.x_current, [1.0, 2.0, 3.0]);
@@ -194,4 +273,40 @@ mod tests {
         oc.objective_current = 2.34;
         assert_eq!(oc.objective_current, 2.34);
     }
+
+    #[test]
+    fn bfgs_resets_without_positive_curvature() {
+        let nlp = NlpBuilder::new()
+            .objective(|xs| xs[0] + xs[1])
+            .grad_objective(|_| vec![1.0, 1.0])
+            .initial_guess(vec![0.0, 0.0])
+            .build()
+            .unwrap();
+        let bounds_handler = BarrierBoundsHandler {
+            bounds: nlp.bounds(),
+            barrier_parameter: 0.0,
+            barrier_decrease_factor: 0.5,
+        };
+        let constraints_handler = AugmentedLagrangianConstraintHandler {
+            mu: vec![],
+            lambda: vec![],
+            c: 1.0,
+        };
+        let mut bfgs = Bfgs::new(&nlp);
+        let mut context = bfgs.initialize(&nlp, &bounds_handler, &constraints_handler);
+        let mut direction = vec![0.0; 2];
+
+        context.iteration = 1;
+        context.objective_grad = vec![1.0, 0.0];
+        bfgs.iterate(&nlp, &mut context, &mut direction);
+        assert_eq!(direction, [-1.0, 0.0]);
+
+        // the gradient grows along the descent direction
+        context.iteration = 2;
+        context.objective_grad = vec![2.0, 0.0];
+        bfgs.iterate(&nlp, &mut context, &mut direction);
+
+        assert_eq!(Optimizer::<ClosureNlp>::num_resets(&bfgs), 1);
+        assert_eq!(direction, [-2.0, 0.0]);
+    }
 }
@@ -0,0 +1,255 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use toml::value::Table;
+use toml::Value;
+
+use crate::options::OptionsValidationError;
+use crate::Options;
+
+const ENVIRONMENT_PREFIX: &str = "RUNOLINOP_";
+const SECTIONS: [&str; 6] = [
+    "step_size_control",
+    "bounds_handler",
+    "constraints_handler",
+    "logger",
+    "termination",
+    "history",
+];
+
+#[derive(Debug)]
+pub enum OptionsError {
+    Io(io::Error),
+    /// Malformed file, unknown key or value of the wrong type.
+    Parse(String),
+    Invalid(OptionsValidationError),
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionsError::Io(error) => write!(f, "cannot read options: {}", error),
+            OptionsError::Parse(message) => write!(f, "invalid options: {}", message),
+            OptionsError::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+impl From<OptionsValidationError> for OptionsError {
+    fn from(error: OptionsValidationError) -> Self {
+        OptionsError::Invalid(error)
+    }
+}
+
+impl From<io::Error> for OptionsError {
+    fn from(error: io::Error) -> Self {
+        OptionsError::Io(error)
+    }
+}
+
+fn parse_error(error: impl fmt::Display) -> OptionsError {
+    OptionsError::Parse(error.to_string())
+}
+
+impl Options {
+    /// Reads options from a TOML file, or a JSON file if its extension is `json`, and applies
+    /// overrides from the environment as [`Options::from_env`] does. Options missing from the
+    /// file keep their defaults.
+    pub fn load(path: impl AsRef<Path>) -> Result<Options, OptionsError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let table = match path.extension() {
+            Some(extension) if extension == "json" => parse_json(&text)?,
+            _ => parse_toml(&text)?,
+        };
+
+        from_table(table, std::env::vars_os())
+    }
+
+    /// Parses options from TOML, e.g. `optimizer = "bfgs"` followed by a `[termination]` table
+    /// with `max_iterations = 100`.
+    pub fn from_toml(text: &str) -> Result<Options, OptionsError> {
+        from_table(parse_toml(text)?, std::iter::empty())
+    }
+
+    /// Parses options from a JSON object laid out like the TOML of [`Options::from_toml`].
+    pub fn from_json(text: &str) -> Result<Options, OptionsError> {
+        from_table(parse_json(text)?, std::iter::empty())
+    }
+
+    /// Default options with overrides from environment variables: `RUNOLINOP_OPTIMIZER` for
+    /// `optimizer` and e.g. `RUNOLINOP_TERMINATION_MAX_ITERATIONS` for
+    /// `termination.max_iterations`. Values are TOML values; bare words are taken as strings.
+    /// `RUNOLINOP_*` variables that are not valid UTF-8 are rejected.
+    pub fn from_env() -> Result<Options, OptionsError> {
+        from_table(Table::new(), std::env::vars_os())
+    }
+}
+
+fn parse_toml(text: &str) -> Result<Table, OptionsError> {
+    toml::from_str(text).map_err(parse_error)
+}
+
+fn parse_json(text: &str) -> Result<Table, OptionsError> {
+    serde_json::from_str(text).map_err(parse_error)
+}
+
+fn from_table(
+    mut table: Table,
+    variables: impl Iterator<Item = (OsString, OsString)>,
+) -> Result<Options, OptionsError> {
+    for (name, value) in variables {
+        let lossy_name = name.to_string_lossy();
+        if !lossy_name.starts_with(ENVIRONMENT_PREFIX) {
+            continue;
+        }
+
+        let (name, value) = match (name.to_str(), value.to_str()) {
+            (Some(name), Some(value)) => (name, value),
+            _ => {
+                return Err(OptionsError::Parse(format!(
+                    "environment variable {} is not valid UTF-8",
+                    lossy_name
+                )))
+            }
+        };
+
+        let key = &name[ENVIRONMENT_PREFIX.len()..];
+        set_override(&mut table, &key.to_lowercase(), parse_value(value));
+    }
+
+    let options: Options = Value::Table(table).try_into().map_err(parse_error)?;
+    options.validate()?;
+
+    Ok(options)
+}
+
+/// Puts `value` at `key`, which names either a top-level option or an option of a section,
+/// joined by an underscore.
+fn set_override(table: &mut Table, key: &str, value: Value) {
+    let section = SECTIONS.iter().find(|section| {
+        key.len() > section.len() + 1
+            && key.starts_with(*section)
+            && key.as_bytes()[section.len()] == b'_'
+    });
+
+    match section {
+        Some(section) => {
+            let section_table = table
+                .entry(section.to_string())
+                .or_insert_with(|| Value::Table(Table::new()));
+
+            if let Value::Table(section_table) = section_table {
+                section_table.insert(key[section.len() + 1..].to_string(), value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+fn parse_value(text: &str) -> Value {
+    toml::from_str::<Table>(&format!("value = {}", text))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::Optimizer;
+
+    fn variables(pairs: &[(&str, &str)]) -> impl Iterator<Item = (OsString, OsString)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (OsString::from(name), OsString::from(value)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn environment_overrides_file() {
+        let table =
+            parse_toml("[termination]\nmax_iterations = 10\ngradient_tolerance = 1e-4").unwrap();
+
+        let options = from_table(
+            table,
+            variables(&[
+                ("RUNOLINOP_TERMINATION_MAX_ITERATIONS", "20"),
+                ("RUNOLINOP_OPTIMIZER", "steepest_descent"),
+                ("RUNOLINOP_STEP_SIZE_CONTROL_ALPHA_0", "2"),
+                ("RUNOLINOP_LOGGER_SCHEDULE", "{ every_nth_iteration = 5 }"),
+                ("PATH", "/usr/bin"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(options.termination.max_iterations, 20);
+        assert_eq!(options.termination.gradient_tolerance, 1.0E-4);
+        assert_eq!(options.optimizer, Optimizer::SteepestDescent);
+        assert_eq!(options.step_size_control.alpha_0, 2.0);
+        assert_eq!(
+            options.logger.schedule,
+            Some(crate::Frequency::EveryNthIteration(5))
+        );
+    }
+
+    #[test]
+    fn time_based_schedule_is_given_in_seconds() {
+        let options = Options::from_toml("[logger]\nschedule = { every = 0.5 }").unwrap();
+        assert_eq!(
+            options.logger.schedule,
+            Some(crate::Frequency::Every(std::time::Duration::from_millis(
+                500
+            )))
+        );
+
+        let json = serde_json::to_string(&options.logger.schedule).unwrap();
+        assert_eq!(json, r#"{"every":0.5}"#);
+
+        assert!(Options::from_toml("[logger]\nschedule = { every = -1.0 }").is_err());
+    }
+
+    #[test]
+    fn unknown_environment_variables_are_rejected() {
+        let result = from_table(
+            Table::new(),
+            variables(&[("RUNOLINOP_TERMINATION_MAX_ITERATION", "20")]),
+        );
+
+        assert!(
+            matches!(result, Err(OptionsError::Parse(message)) if message.contains("max_iteration"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_environment_variables_are_reported() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let invalid = OsString::from_vec(vec![b'1', 0xff]);
+
+        let unrelated = from_table(
+            Table::new(),
+            vec![(OsString::from("OTHER"), invalid.clone())].into_iter(),
+        );
+        assert!(unrelated.is_ok());
+
+        let result = from_table(
+            Table::new(),
+            vec![(
+                OsString::from("RUNOLINOP_TERMINATION_MAX_ITERATIONS"),
+                invalid,
+            )]
+            .into_iter(),
+        );
+        assert!(matches!(result, Err(OptionsError::Parse(message)) if message.contains("UTF-8")));
+    }
+}
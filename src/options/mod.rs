@@ -0,0 +1,261 @@
+use crate::output::Frequency;
+
+#[cfg(feature = "serde")]
+mod loading;
+mod validation;
+
+#[cfg(feature = "serde")]
+pub use loading::OptionsError;
+pub use validation::{InvalidOption, OptionsValidationError};
+
+#[derive(Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Options {
+    pub optimizer: Optimizer,
+    pub step_size_control: StepSizeControl,
+    pub bounds_handler: BoundsHandler,
+    pub constraints_handler: ConstraintsHandler,
+    pub logger: Logger,
+    pub termination: Termination,
+    pub history: History,
+}
+
+/// Algorithm computing the search directions.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Optimizer {
+    #[default]
+    Bfgs,
+    SteepestDescent,
+}
+
+/// Line search choosing the step length along a search direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum StepSizeRule {
+    /// Backtracking until the Armijo-Goldstein condition holds, see
+    /// [`ArmijoGoldsteinRule`](crate::ArmijoGoldsteinRule).
+    #[default]
+    ArmijoGoldstein,
+    /// See [`StrongWolfeRule`](crate::StrongWolfeRule).
+    StrongWolfe,
+    /// Strong Wolfe, falling back to Armijo-Goldstein backtracking where it fails.
+    StrongWolfeThenArmijoGoldstein,
+}
+
+/// How the variable bounds are enforced.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum BoundsStrategy {
+    /// Logarithmic barrier, see [`BarrierBoundsHandler`](crate::BarrierBoundsHandler).
+    #[default]
+    Barrier,
+}
+
+/// How the constraints are enforced.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ConstraintsStrategy {
+    /// See [`AugmentedLagrangianConstraintHandler`](crate::AugmentedLagrangianConstraintHandler).
+    #[default]
+    AugmentedLagrangian,
+}
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[derive(Debug)]
+pub struct StepSizeControl {
+    pub rule: StepSizeRule,
+    pub alpha_0: f64,
+    /// Backtracking factor of the Armijo-Goldstein rule.
+    pub tau: f64,
+    /// Sufficient decrease parameter.
+    pub c: f64,
+    /// Curvature parameter of the strong Wolfe conditions.
+    pub c_curvature: f64,
+}
+
+impl Default for StepSizeControl {
+    fn default() -> Self {
+        Self {
+            rule: Default::default(),
+            alpha_0: 1.0,
+            tau: 0.5,
+            c: 0.2,
+            c_curvature: 0.9,
+        }
+    }
+}
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[derive(Debug)]
+pub struct BoundsHandler {
+    pub strategy: BoundsStrategy,
+    pub barrier_parameter: f64,
+    pub barrier_decrease_factor: f64,
+    /// Moves the initial guess into the interior of the bounds as IPOPT does, by at least
+    /// `bound_push * max(1, |bound|)`, but at most `bound_frac` times the width of the range.
+    pub push_initial_point: bool,
+    pub bound_push: f64,
+    pub bound_frac: f64,
+}
+
+impl Default for BoundsHandler {
+    fn default() -> Self {
+        Self {
+            strategy: Default::default(),
+            barrier_parameter: 1.0E-6,
+            barrier_decrease_factor: 0.5,
+            push_initial_point: false,
+            bound_push: 1.0E-2,
+            bound_frac: 1.0E-2,
+        }
+    }
+}
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[derive(Debug)]
+pub struct ConstraintsHandler {
+    pub strategy: ConstraintsStrategy,
+    pub c: f64,
+}
+
+impl Default for ConstraintsHandler {
+    fn default() -> Self {
+        Self {
+            strategy: Default::default(),
+            c: 1.0E9,
+        }
+    }
+}
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[derive(Debug)]
+pub struct Logger {
+    pub frequency: u32,
+    /// Replaces `frequency` if given.
+    pub schedule: Option<Frequency>,
+    /// Prints a table with a header row every this many lines; 0 prints labelled lines instead.
+    pub header_interval: u32,
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self {
+            frequency: 1,
+            schedule: None,
+            header_interval: 0,
+        }
+    }
+}
+
+/// When the solver stops. Tolerances of zero and limits at their maximum disable the respective
+/// criterion.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[derive(Debug)]
+pub struct Termination {
+    /// Converged once the penalized objective changes by less than this between iterations.
+    pub objective_tolerance: f64,
+    /// Converged once the change of the penalized objective relative to its value drops below this.
+    pub relative_objective_tolerance: f64,
+    /// Converged once the norm of the gradient projected onto the bounds drops below this.
+    pub gradient_tolerance: f64,
+    /// Stalled once the search direction is shorter than this.
+    pub step_tolerance: f64,
+    /// Largest constraint violation that still counts as feasible.
+    pub constraint_tolerance: f64,
+    /// Constrained problems have converged once the Lagrangian gradient norm and the
+    /// complementarity drop below this at a feasible point.
+    pub optimality_tolerance: f64,
+    pub max_iterations: u32,
+    /// Limit on the number of objective evaluations, which also applies within the line search.
+    pub max_evaluations: u32,
+    /// Wall-clock limit in seconds.
+    pub max_wall_time: f64,
+}
+
+impl Default for Termination {
+    fn default() -> Self {
+        Self {
+            objective_tolerance: 1.0E-12,
+            relative_objective_tolerance: 0.0,
+            gradient_tolerance: 1.0E-8,
+            step_tolerance: 1.0E-10,
+            constraint_tolerance: 1.0E-6,
+            optimality_tolerance: 1.0E-6,
+            max_iterations: u32::MAX,
+            max_evaluations: u32::MAX,
+            max_wall_time: f64::INFINITY,
+        }
+    }
+}
+
+/// Recording of the convergence history into [`Solution::history`](crate::Solution::history).
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[derive(Debug)]
+pub struct History {
+    pub enabled: bool,
+    /// Every this many iterations, the iterate itself is recorded as well; 0 records none.
+    pub iterate_stride: u32,
+    /// Upper bound in bytes for the recorded iterates. When it would be exceeded, every other
+    /// recorded iterate is dropped and the stride doubled. The objectives, step lengths and
+    /// constraint violations are recorded for every iteration and not counted.
+    pub max_memory: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            iterate_stride: 1,
+            max_memory: 64 * 1024 * 1024,
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use std::fmt;
+
+use crate::options::StepSizeRule;
+use crate::output::Frequency;
+use crate::Options;
+
+/// An option whose value lies outside of its allowed range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidOption {
+    /// Path of the option, e.g. `bounds_handler.barrier_parameter`.
+    pub option: &'static str,
+    pub value: String,
+    pub allowed: &'static str,
+}
+
+impl fmt::Display for InvalidOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} = {} is outside of {}",
+            self.option, self.value, self.allowed
+        )
+    }
+}
+
+/// All invalid options found by [`Options::validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionsValidationError {
+    pub invalid_options: Vec<InvalidOption>,
+}
+
+impl fmt::Display for OptionsValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid options: ")?;
+
+        for (i, invalid_option) in self.invalid_options.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", invalid_option)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for OptionsValidationError {}
+
+/// Collects the options that fail their check.
+struct Validator {
+    invalid_options: Vec<InvalidOption>,
+}
+
+impl Validator {
+    fn check(
+        &mut self,
+        option: &'static str,
+        value: impl fmt::Display,
+        valid: bool,
+        allowed: &'static str,
+    ) {
+        if !valid {
+            self.invalid_options.push(InvalidOption {
+                option,
+                value: value.to_string(),
+                allowed,
+            });
+        }
+    }
+
+    fn check_tolerance(&mut self, option: &'static str, value: f64) {
+        self.check(option, value, value >= 0.0, "[0, inf]");
+    }
+}
+
+impl Options {
+    /// Checks every option against its allowed range.
+    pub fn validate(&self) -> Result<(), OptionsValidationError> {
+        let mut validator = Validator {
+            invalid_options: vec![],
+        };
+        let v = &mut validator;
+
+        // the ranges are those the step size rules clamp their parameters to
+        let step = &self.step_size_control;
+        v.check(
+            "step_size_control.alpha_0",
+            step.alpha_0,
+            step.alpha_0 >= 1.0E-4 && step.alpha_0.is_finite(),
+            "[1e-4, inf)",
+        );
+        v.check(
+            "step_size_control.tau",
+            step.tau,
+            (1.0E-4..=1.0 - 1.0E-4).contains(&step.tau),
+            "[1e-4, 1 - 1e-4]",
+        );
+        if step.rule == StepSizeRule::ArmijoGoldstein {
+            v.check(
+                "step_size_control.c",
+                step.c,
+                (1.0E-4..=1.0 - 1.0E-4).contains(&step.c),
+                "[1e-4, 1 - 1e-4]",
+            );
+        } else {
+            v.check(
+                "step_size_control.c",
+                step.c,
+                (1.0E-4..=0.5).contains(&step.c),
+                "[1e-4, 0.5]",
+            );
+            v.check(
+                "step_size_control.c_curvature",
+                step.c_curvature,
+                (step.c + 1.0E-4..=1.0 - 1.0E-4).contains(&step.c_curvature),
+                "[step_size_control.c + 1e-4, 1 - 1e-4]",
+            );
+        }
+
+        let bounds = &self.bounds_handler;
+        v.check(
+            "bounds_handler.barrier_parameter",
+            bounds.barrier_parameter,
+            bounds.barrier_parameter >= 0.0 && bounds.barrier_parameter.is_finite(),
+            "[0, inf)",
+        );
+        v.check(
+            "bounds_handler.barrier_decrease_factor",
+            bounds.barrier_decrease_factor,
+            bounds.barrier_decrease_factor > 0.0 && bounds.barrier_decrease_factor < 1.0,
+            "(0, 1)",
+        );
+        v.check(
+            "bounds_handler.bound_push",
+            bounds.bound_push,
+            bounds.bound_push > 0.0 && bounds.bound_push.is_finite(),
+            "(0, inf)",
+        );
+        v.check(
+            "bounds_handler.bound_frac",
+            bounds.bound_frac,
+            bounds.bound_frac > 0.0 && bounds.bound_frac <= 0.5,
+            "(0, 0.5]",
+        );
+
+        let c = self.constraints_handler.c;
+        v.check(
+            "constraints_handler.c",
+            c,
+            c > 0.0 && c.is_finite(),
+            "(0, inf)",
+        );
+
+        let logger = &self.logger;
+        v.check(
+            "logger.frequency",
+            logger.frequency,
+            logger.frequency > 0,
+            "[1, 4294967295]",
+        );
+        match logger.schedule {
+            Some(Frequency::EveryNthIteration(n)) => v.check(
+                "logger.schedule.every_nth_iteration",
+                n,
+                n > 0,
+                "[1, 4294967295]",
+            ),
+            Some(Frequency::OnImprovement(fraction)) => v.check(
+                "logger.schedule.on_improvement",
+                fraction,
+                fraction >= 0.0 && fraction.is_finite(),
+                "[0, inf)",
+            ),
+            _ => {}
+        }
+
+        let termination = &self.termination;
+        v.check_tolerance(
+            "termination.objective_tolerance",
+            termination.objective_tolerance,
+        );
+        v.check_tolerance(
+            "termination.relative_objective_tolerance",
+            termination.relative_objective_tolerance,
+        );
+        v.check_tolerance(
+            "termination.gradient_tolerance",
+            termination.gradient_tolerance,
+        );
+        v.check_tolerance("termination.step_tolerance", termination.step_tolerance);
+        v.check_tolerance(
+            "termination.constraint_tolerance",
+            termination.constraint_tolerance,
+        );
+        v.check_tolerance(
+            "termination.optimality_tolerance",
+            termination.optimality_tolerance,
+        );
+        v.check_tolerance("termination.max_wall_time", termination.max_wall_time);
+
+        if validator.invalid_options.is_empty() {
+            Ok(())
+        } else {
+            Err(OptionsValidationError {
+                invalid_options: validator.invalid_options,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{BoundsHandler, Logger};
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(Options::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn every_invalid_option_is_listed() {
+        let options = Options {
+            bounds_handler: BoundsHandler {
+                barrier_parameter: -1.0,
+                barrier_decrease_factor: 1.0,
+                ..Default::default()
+            },
+            logger: Logger {
+                frequency: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let error = options.validate().unwrap_err();
+
+        assert_eq!(
+            error
+                .invalid_options
+                .iter()
+                .map(|invalid_option| invalid_option.option)
+                .collect::<Vec<_>>(),
+            [
+                "bounds_handler.barrier_parameter",
+                "bounds_handler.barrier_decrease_factor",
+                "logger.frequency"
+            ]
+        );
+        assert_eq!(
+            error.invalid_options[1],
+            InvalidOption {
+                option: "bounds_handler.barrier_decrease_factor",
+                value: "1".to_string(),
+                allowed: "(0, 1)",
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid options: bounds_handler.barrier_parameter = -1 is outside of [0, inf), \
+             bounds_handler.barrier_decrease_factor = 1 is outside of (0, 1), \
+             logger.frequency = 0 is outside of [1, 4294967295]"
+        );
+    }
+
+    #[test]
+    fn curvature_parameter_is_only_checked_for_strong_wolfe() {
+        let mut options = Options::default();
+        options.step_size_control.c = 0.95;
+
+        assert_eq!(options.validate(), Ok(()));
+
+        options.step_size_control.rule = StepSizeRule::StrongWolfe;
+
+        assert_eq!(
+            options
+                .validate()
+                .unwrap_err()
+                .invalid_options
+                .iter()
+                .map(|invalid_option| invalid_option.option)
+                .collect::<Vec<_>>(),
+            ["step_size_control.c", "step_size_control.c_curvature"]
+        );
+    }
+
+    #[test]
+    fn nan_is_invalid() {
+        let mut options = Options::default();
+        options.termination.gradient_tolerance = f64::NAN;
+
+        assert_eq!(
+            options.validate().unwrap_err().invalid_options[0].option,
+            "termination.gradient_tolerance"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use log::{info, log, Level};
+
+use crate::optimizer::OptContext;
+use crate::output::SolverLogger;
+use crate::vec_utils::norm2;
+use crate::{NlpInfo, Solution};
+
+/// Emits the iterations through the `log` crate under the target `runolinop`.
+///
+/// Iterations are logged at the given level, start and end of the solve at `Info`.
+pub struct LogLogger {
+    level: Level,
+    last_output_iteration: u32,
+}
+
+impl LogLogger {
+    pub fn new(level: Level) -> Self {
+        LogLogger {
+            level,
+            last_output_iteration: 0,
+        }
+    }
+}
+
+impl Default for LogLogger {
+    fn default() -> Self {
+        Self::new(Level::Debug)
+    }
+}
+
+impl SolverLogger for LogLogger {
+    fn start(&mut self, info: &NlpInfo) {
+        self.last_output_iteration = 0;
+
+        info!(target: "runolinop", "solving {}", info);
+    }
+
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool) {
+        if ignore_frequency && self.last_output_iteration == context.iteration {
+            return;
+        }
+
+        log!(
+            target: "runolinop",
+            self.level,
+            "iteration {} | objective {} (actual: {}) | change: {} | gradient norm: {} | step length: {} | constraint violation: {}",
+            context.iteration,
+            context.objective_current,
+            context.pure_objective,
+            (context.objective_current - context.objective_previous).abs(),
+            norm2(&context.objective_grad),
+            context.direction_scale_factor,
+            context.optimality.constraint_violation
+        );
+
+        self.last_output_iteration = context.iteration;
+    }
+
+    fn finish(&mut self, solution: &Solution) {
+        info!(
+            target: "runolinop",
+            "{} after {} iterations with objective {}",
+            solution.status,
+            solution.num_iterations,
+            solution.best_objective_value
+        );
+    }
+}
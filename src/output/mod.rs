@@ -0,0 +1,281 @@
+use std::time::Duration;
+
+#[cfg(feature = "log")]
+pub use self::log::LogLogger;
+#[cfg(feature = "tracing")]
+pub use self::tracing::TracingLogger;
+use crate::optimizer::OptContext;
+use crate::output::Frequency::{Every, EveryNthIteration, Geometric, OnImprovement};
+use crate::{NlpInfo, Solution};
+pub use writers::{CsvLogger, JsonLinesLogger};
+
+#[cfg(feature = "log")]
+mod log;
+#[cfg(feature = "tracing")]
+mod tracing;
+mod writers;
+
+/// When a logger writes an iteration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Frequency {
+    EveryNthIteration(u32),
+    /// Whenever at least this much wall-clock time has passed since the last output. Serialized
+    /// as seconds, e.g. `every = 0.5`.
+    Every(#[cfg_attr(feature = "serde", serde(with = "seconds"))] Duration),
+    /// Whenever the penalized objective improved by more than this fraction (0.01 for 1%) since
+    /// the last output.
+    OnImprovement(f64),
+    /// In iterations 1, 2, 4, 8, ...
+    Geometric,
+}
+
+#[cfg(feature = "serde")]
+mod seconds {
+    use std::time::Duration;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let seconds = f64::deserialize(deserializer)?;
+        Duration::try_from_secs_f64(seconds)
+            .map_err(|_| D::Error::custom(format!("invalid number of seconds: {}", seconds)))
+    }
+}
+
+/// Decides for a [`Frequency`] which iterations are due for output.
+struct Throttle {
+    frequency: Frequency,
+    last_output_iteration: u32,
+    last_output_elapsed: Duration,
+    last_output_objective: f64,
+}
+
+impl Throttle {
+    fn new(frequency: Frequency) -> Self {
+        Throttle {
+            frequency,
+            last_output_iteration: 0,
+            last_output_elapsed: Duration::from_secs(0),
+            last_output_objective: f64::INFINITY,
+        }
+    }
+
+    fn due(&mut self, context: &OptContext, ignore_frequency: bool) -> bool {
+        let due = if ignore_frequency {
+            self.last_output_iteration != context.iteration
+        } else {
+            match self.frequency {
+                EveryNthIteration(n) => n != 0 && context.iteration % n == 0,
+                Every(interval) => {
+                    context.elapsed.saturating_sub(self.last_output_elapsed) >= interval
+                }
+                OnImprovement(fraction) => {
+                    self.last_output_objective.is_infinite()
+                        || self.last_output_objective - context.objective_current
+                            > fraction * self.last_output_objective.abs()
+                }
+                Geometric => context.iteration.is_power_of_two(),
+            }
+        };
+
+        if due {
+            self.last_output_iteration = context.iteration;
+            self.last_output_elapsed = context.elapsed;
+            self.last_output_objective = context.objective_current;
+        }
+
+        due
+    }
+}
+
+pub trait SolverLogger {
+    /// Called once before the first iteration.
+    fn start(&mut self, _info: &NlpInfo) {}
+
+    /// Called after every iteration, and once more with `ignore_frequency` set when the solve
+    /// ends.
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool);
+
+    /// Called with the result once the solve has ended.
+    fn finish(&mut self, _solution: &Solution) {}
+}
+
+/// Lets a logger be inspected after the solve, e.g. for the write error of a [`CsvLogger`].
+impl<L: SolverLogger + ?Sized> SolverLogger for &mut L {
+    fn start(&mut self, info: &NlpInfo) {
+        (**self).start(info)
+    }
+
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool) {
+        (**self).log(context, ignore_frequency)
+    }
+
+    fn finish(&mut self, solution: &Solution) {
+        (**self).finish(solution)
+    }
+}
+
+/// Prints iterations to stdout.
+///
+/// Without a header, every line labels its values. With a header interval, the values are printed
+/// as a table whose header row is repeated every that many lines, as IPOPT does.
+pub struct StdoutLogger {
+    throttle: Throttle,
+    header_interval: u32,
+    lines_since_header: u32,
+}
+
+impl StdoutLogger {
+    #[allow(dead_code)]
+    pub fn new(n: u32) -> Self {
+        Self::with_frequency(EveryNthIteration(n))
+    }
+
+    pub fn with_frequency(frequency: Frequency) -> Self {
+        StdoutLogger {
+            throttle: Throttle::new(frequency),
+            header_interval: 0,
+            lines_since_header: 0,
+        }
+    }
+
+    /// Switches to table output with a header row every `lines` lines; 0 switches back to labelled
+    /// lines.
+    pub fn with_header_interval(mut self, lines: u32) -> Self {
+        self.header_interval = lines;
+        self
+    }
+}
+
+impl SolverLogger for StdoutLogger {
+    fn start(&mut self, _info: &NlpInfo) {
+        self.throttle = Throttle::new(self.throttle.frequency);
+        self.lines_since_header = 0;
+    }
+
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool) {
+        if !self.throttle.due(context, ignore_frequency) {
+            return;
+        }
+
+        let change = (context.objective_current - context.objective_previous).abs();
+
+        if self.header_interval == 0 {
+            println!(
+                "iteration {:5} | objective {:20.8} (actual: {:14.8}) | change: {:14.8} | kkt: {:10.3e} | infeasibility: {:10.3e}",
+                context.iteration,
+                context.objective_current,
+                context.pure_objective,
+                change,
+                context.optimality.lagrangian_gradient_norm,
+                context.optimality.constraint_violation
+            );
+            return;
+        }
+
+        if self.lines_since_header % self.header_interval == 0 {
+            println!(
+                "{:>5} {:>20} {:>14} {:>14} {:>10} {:>10} {:>10}",
+                "iter", "objective", "actual", "change", "kkt", "inf_pr", "alpha"
+            );
+        }
+
+        println!(
+            "{:5} {:20.8} {:14.8} {:14.8} {:10.3e} {:10.3e} {:10.3e}",
+            context.iteration,
+            context.objective_current,
+            context.pure_objective,
+            change,
+            context.optimality.lagrangian_gradient_norm,
+            context.optimality.constraint_violation,
+            context.direction_scale_factor
+        );
+
+        self.lines_since_header += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(iteration: u32, objective: f64, elapsed_millis: u64) -> OptContext {
+        OptContext {
+            iteration,
+            x_current: vec![0.0],
+            x_previous: vec![0.0],
+            objective_current: objective,
+            objective_previous: f64::INFINITY,
+            pure_objective: objective,
+            objective_grad: vec![0.0],
+            direction_scale_factor: 1.0,
+            optimality: Default::default(),
+            barrier_parameter: 0.0,
+            penalty_parameter: 0.0,
+            elapsed: Duration::from_millis(elapsed_millis),
+        }
+    }
+
+    fn due_iterations(frequency: Frequency, contexts: &[OptContext]) -> Vec<u32> {
+        let mut throttle = Throttle::new(frequency);
+
+        contexts
+            .iter()
+            .filter(|context| throttle.due(context, false))
+            .map(|context| context.iteration)
+            .collect()
+    }
+
+    #[test]
+    fn geometric_schedule() {
+        let contexts: Vec<_> = (1..=20).map(|i| context(i, 0.0, 0)).collect();
+
+        assert_eq!(due_iterations(Geometric, &contexts), [1, 2, 4, 8, 16]);
+        assert_eq!(
+            due_iterations(EveryNthIteration(5), &contexts),
+            [5, 10, 15, 20]
+        );
+    }
+
+    #[test]
+    fn time_based_schedule() {
+        let contexts: Vec<_> = (1..=10).map(|i| context(i, 0.0, 700 * i as u64)).collect();
+
+        assert_eq!(
+            due_iterations(Every(Duration::from_secs(2)), &contexts),
+            [3, 6, 9]
+        );
+    }
+
+    #[test]
+    fn improvement_based_schedule() {
+        let objectives = [100.0, 95.0, 89.0, 88.0, 50.0, 49.9];
+        let contexts: Vec<_> = objectives
+            .iter()
+            .enumerate()
+            .map(|(i, objective)| context(i as u32 + 1, *objective, 0))
+            .collect();
+
+        assert_eq!(due_iterations(OnImprovement(0.1), &contexts), [1, 3, 5]);
+    }
+
+    #[test]
+    fn final_iteration_is_written_once() {
+        let mut throttle = Throttle::new(EveryNthIteration(2));
+
+        assert!(throttle.due(&context(2, 0.0, 0), false));
+        assert!(!throttle.due(&context(2, 0.0, 0), true));
+        assert!(!throttle.due(&context(3, 0.0, 0), false));
+        assert!(throttle.due(&context(3, 0.0, 0), true));
+    }
+}
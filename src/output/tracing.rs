@@ -0,0 +1,69 @@
+use tracing::{event, info, Level};
+
+use crate::optimizer::OptContext;
+use crate::output::SolverLogger;
+use crate::vec_utils::norm2;
+use crate::{NlpInfo, Solution};
+
+/// Emits every iteration as a structured `tracing` event with one field per quantity.
+///
+/// The events are recorded inside the spans the solver opens when the `tracing` feature is
+/// enabled: `solve`, `outer_iteration` for each augmented-Lagrangian update and `line_search`.
+#[derive(Default)]
+pub struct TracingLogger {
+    last_output_iteration: u32,
+}
+
+impl TracingLogger {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl SolverLogger for TracingLogger {
+    fn start(&mut self, info: &NlpInfo) {
+        self.last_output_iteration = 0;
+
+        info!(
+            target: "runolinop",
+            num_variables = info.num_variables,
+            num_inequality_constraints = info.num_inequality_constraints,
+            num_equality_constraints = info.num_equality_constraints,
+            "solve started"
+        );
+    }
+
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool) {
+        if ignore_frequency && self.last_output_iteration == context.iteration {
+            return;
+        }
+
+        event!(
+            target: "runolinop",
+            Level::DEBUG,
+            iteration = context.iteration,
+            objective = context.objective_current,
+            pure_objective = context.pure_objective,
+            objective_change = (context.objective_current - context.objective_previous).abs(),
+            gradient_norm = norm2(&context.objective_grad),
+            step_length = context.direction_scale_factor,
+            barrier_parameter = context.barrier_parameter,
+            penalty_parameter = context.penalty_parameter,
+            constraint_violation = context.optimality.constraint_violation,
+            elapsed_time = context.elapsed.as_secs_f64(),
+            "iteration"
+        );
+
+        self.last_output_iteration = context.iteration;
+    }
+
+    fn finish(&mut self, solution: &Solution) {
+        info!(
+            target: "runolinop",
+            status = %solution.status,
+            iterations = solution.num_iterations,
+            objective = solution.best_objective_value,
+            "solve finished"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::io::{self, Write};
+
+use crate::optimizer::OptContext;
+use crate::output::SolverLogger;
+use crate::vec_utils::norm2;
+use crate::{NlpInfo, Solution};
+
+const COLUMNS: [&str; 10] = [
+    "iteration",
+    "objective",
+    "pure_objective",
+    "objective_change",
+    "gradient_norm",
+    "step_length",
+    "barrier_parameter",
+    "penalty_parameter",
+    "constraint_violation",
+    "elapsed_time",
+];
+
+fn values(context: &OptContext) -> [f64; 9] {
+    [
+        context.objective_current,
+        context.pure_objective,
+        (context.objective_current - context.objective_previous).abs(),
+        norm2(&context.objective_grad),
+        context.direction_scale_factor,
+        context.barrier_parameter,
+        context.penalty_parameter,
+        context.optimality.constraint_violation,
+        context.elapsed.as_secs_f64(),
+    ]
+}
+
+/// Shared bookkeeping of the file loggers: every iteration is written once, and the first write
+/// error disables the logger and is kept since `SolverLogger` cannot pass it on.
+struct Rows<W: Write> {
+    writer: W,
+    last_written_iteration: u32,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Rows<W> {
+    fn new(writer: W) -> Self {
+        Rows {
+            writer,
+            last_written_iteration: 0,
+            error: None,
+        }
+    }
+
+    fn write(&mut self, write: impl FnOnce(&mut W) -> io::Result<()>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(error) = write(&mut self.writer) {
+            self.error = Some(error);
+        }
+    }
+
+    /// Forgets the iterations of the previous solve; a write error is kept.
+    fn restart(&mut self) {
+        self.last_written_iteration = 0;
+    }
+
+    fn write_iteration(
+        &mut self,
+        context: &OptContext,
+        ignore_frequency: bool,
+        write: impl FnOnce(&mut W) -> io::Result<()>,
+    ) {
+        if ignore_frequency && self.last_written_iteration == context.iteration {
+            return;
+        }
+
+        self.write(write);
+        self.last_written_iteration = context.iteration;
+    }
+}
+
+/// Writes one CSV row per iteration, preceded by a header row.
+pub struct CsvLogger<W: Write> {
+    rows: Rows<W>,
+}
+
+impl<W: Write> CsvLogger<W> {
+    pub fn new(writer: W) -> Self {
+        CsvLogger {
+            rows: Rows::new(writer),
+        }
+    }
+
+    /// The write error that stopped the logger, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.rows.error.as_ref()
+    }
+}
+
+impl<W: Write> SolverLogger for CsvLogger<W> {
+    fn start(&mut self, _info: &NlpInfo) {
+        self.rows.restart();
+        self.rows
+            .write(|writer| writeln!(writer, "{}", COLUMNS.join(",")));
+    }
+
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool) {
+        self.rows
+            .write_iteration(context, ignore_frequency, |writer| {
+                write!(writer, "{}", context.iteration)?;
+
+                for value in values(context).iter() {
+                    write!(writer, ",{}", value)?;
+                }
+
+                writeln!(writer)
+            });
+    }
+
+    fn finish(&mut self, _solution: &Solution) {
+        self.rows.write(|writer| writer.flush());
+    }
+}
+
+/// Writes one JSON object per line and iteration. Infinite and NaN values become `null`.
+pub struct JsonLinesLogger<W: Write> {
+    rows: Rows<W>,
+}
+
+impl<W: Write> JsonLinesLogger<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLinesLogger {
+            rows: Rows::new(writer),
+        }
+    }
+
+    /// The write error that stopped the logger, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.rows.error.as_ref()
+    }
+}
+
+impl<W: Write> SolverLogger for JsonLinesLogger<W> {
+    fn start(&mut self, _info: &NlpInfo) {
+        self.rows.restart();
+    }
+
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool) {
+        self.rows
+            .write_iteration(context, ignore_frequency, |writer| {
+                write!(writer, "{{\"{}\":{}", COLUMNS[0], context.iteration)?;
+
+                for (name, value) in COLUMNS[1..].iter().zip(values(context).iter()) {
+                    if value.is_finite() {
+                        write!(writer, ",\"{}\":{}", name, value)?;
+                    } else {
+                        write!(writer, ",\"{}\":null", name)?;
+                    }
+                }
+
+                writeln!(writer, "}}")
+            });
+    }
+
+    fn finish(&mut self, _solution: &Solution) {
+        self.rows.write(|writer| writer.flush());
+    }
+}
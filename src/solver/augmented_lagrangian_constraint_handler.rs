@@ -17,7 +17,7 @@ impl AugmentedLagrangianConstraintHandler {
     pub fn adapted_objective_value(&self, f: f64, g: &[f64], h: &[f64]) -> f64 {
         let mut obj = f;
 
-        if h.len() > 0 {
+        if !h.is_empty() {
             obj += inner_product(&self.lambda, h).unwrap() + 0.5 * self.c * norm2_sqr(h);
         }
 
@@ -37,48 +37,50 @@ impl AugmentedLagrangianConstraintHandler {
         h: &[f64],
         grad_h: &[&[f64]],
     ) -> Vec<f64> {
-        let grad_ineq = {
-            let mut grad_ineq = vec![0.0; grad_f.len()];
+        let mut grad = grad_f.to_vec();
+        self.add_penalty_gradient(
+            &mut grad,
+            g,
+            grad_g.iter().copied(),
+            h,
+            grad_h.iter().copied(),
+        );
+
+        grad
+    }
 
-            for (j, grad_g_j) in grad_g.iter().enumerate() {
-                let factor = 0.0_f64.max(self.mu[j] + self.c * g[j]);
+    /// Adds the gradient of the penalty terms to `grad`, given the constraint gradients row by row.
+    pub fn add_penalty_gradient<'r>(
+        &self,
+        grad: &mut [f64],
+        g: &[f64],
+        grad_g: impl Iterator<Item = &'r [f64]>,
+        h: &[f64],
+        grad_h: impl Iterator<Item = &'r [f64]>,
+    ) {
+        for (j, grad_g_j) in grad_g.enumerate() {
+            let factor = 0.0_f64.max(self.mu[j] + self.c * g[j]);
 
-                for (i, grad_ineq_i) in grad_ineq.iter_mut().enumerate() {
-                    *grad_ineq_i += factor * grad_g_j[i];
-                }
+            for (grad_i, grad_g_j_i) in grad.iter_mut().zip(grad_g_j.iter()) {
+                *grad_i += factor * grad_g_j_i;
             }
+        }
 
-            grad_ineq
-        };
-
-        let grad_eq = {
-            let mut grad_eq = vec![0.0; grad_f.len()];
-
-            for (j, grad_h_j) in grad_h.iter().enumerate() {
-                let factor = self.lambda[j] + self.c * h[j];
+        for (j, grad_h_j) in grad_h.enumerate() {
+            let factor = self.lambda[j] + self.c * h[j];
 
-                for (i, grad_eq_i) in grad_eq.iter_mut().enumerate() {
-                    *grad_eq_i += factor * grad_h_j[i];
-                }
+            for (grad_i, grad_h_j_i) in grad.iter_mut().zip(grad_h_j.iter()) {
+                *grad_i += factor * grad_h_j_i;
             }
-
-            grad_eq
-        };
-
-        grad_f
-            .iter()
-            .zip(grad_ineq.iter())
-            .zip(grad_eq.iter())
-            .map(|((grad_f_i, grad_g_i), grad_h_i)| grad_f_i + grad_g_i + grad_h_i)
-            .collect()
+        }
     }
 
     #[allow(dead_code)]
     pub fn update_multipliers(&mut self, g: &[f64], h: &[f64]) {
         let c = self.c;
 
-        for mu in self.mu.iter_mut() {
-            *mu += g.iter().fold(0.0, |sum, g_j| sum + (c * g_j).max(-*mu));
+        for (mu, g_j) in self.mu.iter_mut().zip(g) {
+            *mu = (*mu + c * g_j).max(0.0);
         }
 
         for (lambda, h_j) in self.lambda.iter_mut().zip(h) {
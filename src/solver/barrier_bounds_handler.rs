@@ -26,27 +26,147 @@ impl BarrierBoundsHandler {
     }
 
     pub fn adapted_objective_gradient(&self, xs: &[f64], pure_objective_grad: &[f64]) -> Vec<f64> {
-        pure_objective_grad
-            .iter()
-            .zip(self.bounds.iter())
-            .zip(xs.iter())
-            .map(|((grad_obj, bounds), x)| {
-                let mut grad_barrier_term = 0.0;
+        let mut grad = pure_objective_grad.to_vec();
+        self.add_barrier_gradient(xs, &mut grad);
 
-                if bounds.lb > f64::NEG_INFINITY {
-                    grad_barrier_term -= self.barrier_parameter * (1.0 / (x - bounds.lb));
-                }
+        grad
+    }
 
-                if bounds.ub < f64::INFINITY {
-                    grad_barrier_term += self.barrier_parameter * (1.0 / (bounds.ub - x));
-                }
+    pub fn add_barrier_gradient(&self, xs: &[f64], grad: &mut [f64]) {
+        for ((grad_obj, bounds), x) in grad.iter_mut().zip(self.bounds.iter()).zip(xs.iter()) {
+            let mut grad_barrier_term = 0.0;
+
+            if bounds.lb > f64::NEG_INFINITY {
+                grad_barrier_term -= self.barrier_parameter * (1.0 / (x - bounds.lb));
+            }
+
+            if bounds.ub < f64::INFINITY {
+                grad_barrier_term += self.barrier_parameter * (1.0 / (bounds.ub - x));
+            }
+
+            *grad_obj += grad_barrier_term;
+        }
+    }
 
-                grad_obj + grad_barrier_term
+    /// Multiple of `direction` at which `xs` reaches the nearest bound, where the barrier ends.
+    pub fn max_step(&self, xs: &[f64], direction: &[f64]) -> f64 {
+        xs.iter()
+            .zip(direction.iter())
+            .zip(self.bounds.iter())
+            .fold(f64::INFINITY, |max_step, ((x, d), bounds)| {
+                if *d < 0.0 && bounds.lb > f64::NEG_INFINITY {
+                    max_step.min((bounds.lb - x) / d)
+                } else if *d > 0.0 && bounds.ub < f64::INFINITY {
+                    max_step.min((bounds.ub - x) / d)
+                } else {
+                    max_step
+                }
             })
-            .collect()
     }
 
     pub fn update_barrier_parameter(&mut self) {
         self.barrier_parameter *= self.barrier_decrease_factor;
     }
+
+    /// Replaces omitted (NaN) entries of `xs` by the midpoint of finite bounds, by a distance of
+    /// `max(1, |bound|)` from a single finite bound, or by 0 for free variables.
+    pub(crate) fn fill_omitted(&self, xs: &mut [f64]) {
+        for (x, bounds) in xs.iter_mut().zip(self.bounds.iter()) {
+            if !x.is_nan() {
+                continue;
+            }
+
+            *x = match (bounds.lb.is_finite(), bounds.ub.is_finite()) {
+                (true, true) => 0.5 * (bounds.lb + bounds.ub),
+                (true, false) => bounds.lb + bounds.lb.abs().max(1.0),
+                (false, true) => bounds.ub - bounds.ub.abs().max(1.0),
+                (false, false) => 0.0,
+            };
+        }
+    }
+}
+
+/// IPOPT's rule for moving the initial point into the interior of the bounds: every variable
+/// keeps a distance of at least `bound_push * max(1, |bound|)` from its bounds, but no more than
+/// `bound_frac` times the width of its range.
+pub(crate) struct BoundPush {
+    pub(crate) bound_push: f64,
+    pub(crate) bound_frac: f64,
+}
+
+impl BoundPush {
+    pub(crate) fn apply(&self, xs: &mut [f64], bounds: &[VariableBounds]) {
+        for (x, bounds) in xs.iter_mut().zip(bounds.iter()) {
+            let width = bounds.ub - bounds.lb;
+
+            if bounds.lb.is_finite() {
+                let push =
+                    (self.bound_push * bounds.lb.abs().max(1.0)).min(self.bound_frac * width);
+                *x = x.max(bounds.lb + push);
+            }
+
+            if bounds.ub.is_finite() {
+                let push =
+                    (self.bound_push * bounds.ub.abs().max(1.0)).min(self.bound_frac * width);
+                *x = x.min(bounds.ub - push);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> Vec<VariableBounds> {
+        vec![
+            VariableBounds { lb: 0.0, ub: 1.0 },
+            VariableBounds {
+                lb: 10.0,
+                ub: f64::INFINITY,
+            },
+            VariableBounds {
+                lb: f64::NEG_INFINITY,
+                ub: -0.5,
+            },
+            VariableBounds {
+                lb: f64::NEG_INFINITY,
+                ub: f64::INFINITY,
+            },
+        ]
+    }
+
+    #[test]
+    fn omitted_entries_are_filled_within_bounds() {
+        let bounds_handler = BarrierBoundsHandler {
+            bounds: bounds(),
+            barrier_parameter: 1.0,
+            barrier_decrease_factor: 0.5,
+        };
+        let mut xs = [f64::NAN, f64::NAN, f64::NAN, f64::NAN];
+
+        bounds_handler.fill_omitted(&mut xs);
+
+        assert_eq!(xs, [0.5, 20.0, -1.5, 0.0]);
+    }
+
+    #[test]
+    fn bound_push_follows_ipopt() {
+        let bound_push = BoundPush {
+            bound_push: 1.0E-2,
+            bound_frac: 1.0E-2,
+        };
+        let mut xs = [1.0, 5.0, 0.0, 3.0];
+
+        bound_push.apply(&mut xs, &bounds());
+
+        for (x, expected) in xs.iter().zip([0.99, 10.1, -0.51, 3.0].iter()) {
+            assert!((x - expected).abs() < 1.0E-12);
+        }
+
+        let mut xs = [0.5];
+        bound_push.apply(&mut xs, &[VariableBounds { lb: 0.0, ub: 1.0 }]);
+
+        assert_eq!(xs, [0.5]);
+    }
 }
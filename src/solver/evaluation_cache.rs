@@ -0,0 +1,387 @@
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::{Evaluation, EvaluationError, NlpInfo, NLP};
+
+const CAPACITY: usize = 4;
+
+/// Number of times the solver asked the NLP for each quantity.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EvaluationCounts {
+    pub objective: u32,
+    pub gradient: u32,
+    pub constraints: u32,
+    pub constraint_gradients: u32,
+    pub cache_hits: u32,
+}
+
+impl fmt::Display for EvaluationCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "objective: {}, gradient: {}, constraints: {}, constraint gradients: {}, cache hits: {}",
+            self.objective,
+            self.gradient,
+            self.constraints,
+            self.constraint_gradients,
+            self.cache_hits
+        )
+    }
+}
+
+struct Entry {
+    xs: Vec<f64>,
+    evaluation: Evaluation,
+    valid: bool,
+    has_derivatives: bool,
+    pinned: bool,
+    last_used: u64,
+}
+
+/// Remembers the evaluations at the most recently visited points, so that the line search, the
+/// gradient computation and the multiplier update do not evaluate the same point repeatedly.
+///
+/// The point whose derivatives were requested last, i.e. the current iterate, is never evicted in
+/// favor of line-search trial points. All buffers are allocated up front.
+///
+/// Once the objective has been evaluated `max_evaluations` times, failed evaluations included,
+/// points that are not cached anymore are rejected with an [`EvaluationError`] and
+/// [`EvaluationCache::exhausted`] is set.
+pub struct EvaluationCache {
+    entries: RefCell<Vec<Entry>>,
+    counts: Cell<EvaluationCounts>,
+    clock: Cell<u64>,
+    max_evaluations: u32,
+    exhausted: Cell<bool>,
+    callback_time: Cell<Duration>,
+}
+
+impl EvaluationCache {
+    pub fn new(info: &NlpInfo) -> Self {
+        EvaluationCache {
+            entries: RefCell::new(
+                (0..CAPACITY)
+                    .map(|_| Entry {
+                        xs: vec![0.0; info.num_variables as usize],
+                        evaluation: Evaluation::new(info),
+                        valid: false,
+                        has_derivatives: false,
+                        pinned: false,
+                        last_used: 0,
+                    })
+                    .collect(),
+            ),
+            counts: Cell::new(Default::default()),
+            clock: Cell::new(0),
+            max_evaluations: u32::MAX,
+            exhausted: Cell::new(false),
+            callback_time: Cell::new(Duration::ZERO),
+        }
+    }
+
+    pub fn with_max_evaluations(mut self, max_evaluations: u32) -> Self {
+        self.max_evaluations = max_evaluations;
+        self
+    }
+
+    /// Forgets all points, counts, timings and the exhaustion of a previous solve.
+    pub fn reset(&mut self) {
+        for entry in self.entries.get_mut().iter_mut() {
+            entry.valid = false;
+            entry.has_derivatives = false;
+            entry.pinned = false;
+            entry.last_used = 0;
+        }
+
+        self.counts.set(Default::default());
+        self.clock.set(0);
+        self.exhausted.set(false);
+        self.callback_time.set(Duration::ZERO);
+    }
+
+    pub fn counts(&self) -> EvaluationCounts {
+        self.counts.get()
+    }
+
+    /// Wall-clock time spent in the NLP's callbacks.
+    pub fn callback_time(&self) -> Duration {
+        self.callback_time.get()
+    }
+
+    /// Whether a point was rejected because the evaluation limit was reached.
+    pub fn exhausted(&self) -> bool {
+        self.exhausted.get()
+    }
+
+    /// Returns the evaluation at `xs`; its derivative buffers are only meaningful if
+    /// `with_derivatives` is set.
+    pub fn evaluate<N: NLP>(
+        &self,
+        nlp: &N,
+        xs: &[f64],
+        with_derivatives: bool,
+    ) -> Result<Ref<'_, Evaluation>, EvaluationError> {
+        let mut entries = self.entries.borrow_mut();
+        let mut counts = self.counts.get();
+        let has_constraints =
+            nlp.info().num_inequality_constraints > 0 || nlp.info().num_equality_constraints > 0;
+
+        let i = match entries
+            .iter()
+            .position(|entry| entry.valid && entry.xs == xs)
+        {
+            Some(i) => {
+                let entry = &mut entries[i];
+
+                if with_derivatives && !entry.has_derivatives {
+                    let evaluation = &mut entry.evaluation;
+
+                    counts.gradient += 1;
+                    counts.constraint_gradients += has_constraints as u32;
+                    self.counts.set(counts);
+
+                    let start = Instant::now();
+                    let result = nlp
+                        .grad_objective_into(xs, &mut evaluation.grad_objective)
+                        .and_then(|()| {
+                            nlp.grad_inequality_constraints_into(
+                                xs,
+                                &mut evaluation.grad_inequality_constraints,
+                            )
+                        })
+                        .and_then(|()| {
+                            nlp.grad_equality_constraints_into(
+                                xs,
+                                &mut evaluation.grad_equality_constraints,
+                            )
+                        });
+                    self.add_callback_time(start);
+                    result?;
+
+                    entry.has_derivatives = true;
+                } else {
+                    counts.cache_hits += 1;
+                }
+
+                i
+            }
+            None => {
+                if counts.objective >= self.max_evaluations {
+                    self.exhausted.set(true);
+                    return Err(EvaluationError::new(
+                        "maximum number of evaluations reached",
+                    ));
+                }
+
+                let i = entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| !entry.pinned)
+                    .min_by_key(|(_, entry)| (entry.valid, entry.last_used))
+                    .map(|(i, _)| i)
+                    .unwrap();
+                let entry = &mut entries[i];
+
+                // failed evaluations count as well
+                counts.objective += 1;
+                counts.constraints += has_constraints as u32;
+
+                if with_derivatives {
+                    counts.gradient += 1;
+                    counts.constraint_gradients += has_constraints as u32;
+                }
+
+                self.counts.set(counts);
+
+                entry.valid = false;
+                entry.xs.copy_from_slice(xs);
+                let start = Instant::now();
+                let result = nlp.evaluate(xs, with_derivatives, &mut entry.evaluation);
+                self.add_callback_time(start);
+                result?;
+                entry.valid = true;
+                entry.has_derivatives = with_derivatives;
+
+                i
+            }
+        };
+
+        if with_derivatives {
+            for (j, entry) in entries.iter_mut().enumerate() {
+                entry.pinned = i == j;
+            }
+        }
+
+        self.clock.set(self.clock.get() + 1);
+        entries[i].last_used = self.clock.get();
+        self.counts.set(counts);
+
+        drop(entries);
+
+        Ok(Ref::map(self.entries.borrow(), |entries| {
+            &entries[i].evaluation
+        }))
+    }
+
+    fn add_callback_time(&self, start: Instant) {
+        self.callback_time
+            .set(self.callback_time.get() + start.elapsed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VariableBounds;
+
+    struct Parabola {
+        info: NlpInfo,
+    }
+
+    impl NLP for Parabola {
+        fn info(&self) -> &NlpInfo {
+            &self.info
+        }
+
+        fn bounds(&self) -> Vec<VariableBounds> {
+            vec![VariableBounds {
+                lb: f64::NEG_INFINITY,
+                ub: f64::INFINITY,
+            }]
+        }
+
+        fn objective(&self, xs: &[f64]) -> f64 {
+            xs[0].powi(2)
+        }
+
+        fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+            vec![2.0 * xs[0]]
+        }
+
+        fn initial_guess(&self) -> Vec<f64> {
+            vec![1.0]
+        }
+    }
+
+    #[test]
+    fn each_quantity_is_evaluated_once_per_point() {
+        let nlp = Parabola {
+            info: NlpInfo {
+                num_variables: 1,
+                num_inequality_constraints: 0,
+                num_equality_constraints: 0,
+            },
+        };
+        let cache = EvaluationCache::new(&nlp.info);
+
+        assert_eq!(cache.evaluate(&nlp, &[3.0], false).unwrap().objective, 9.0);
+        assert_eq!(cache.evaluate(&nlp, &[3.0], false).unwrap().objective, 9.0);
+
+        assert_eq!(
+            cache.evaluate(&nlp, &[3.0], true).unwrap().grad_objective,
+            [6.0]
+        );
+
+        assert!(cache.evaluate(&nlp, &[3.0], true).is_ok());
+
+        assert_eq!(
+            cache.counts(),
+            EvaluationCounts {
+                objective: 1,
+                gradient: 1,
+                constraints: 0,
+                constraint_gradients: 0,
+                cache_hits: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn least_recently_used_points_are_evicted() {
+        let nlp = Parabola {
+            info: NlpInfo {
+                num_variables: 1,
+                num_inequality_constraints: 0,
+                num_equality_constraints: 0,
+            },
+        };
+        let cache = EvaluationCache::new(&nlp.info);
+
+        for x in 0..=CAPACITY {
+            cache.evaluate(&nlp, &[x as f64], false).unwrap();
+        }
+
+        cache.evaluate(&nlp, &[CAPACITY as f64], false).unwrap();
+        assert_eq!(cache.counts().cache_hits, 1);
+
+        cache.evaluate(&nlp, &[0.0], false).unwrap();
+        assert_eq!(cache.counts().objective, CAPACITY as u32 + 2);
+    }
+
+    #[test]
+    fn current_iterate_is_kept() {
+        let nlp = Parabola {
+            info: NlpInfo {
+                num_variables: 1,
+                num_inequality_constraints: 0,
+                num_equality_constraints: 0,
+            },
+        };
+        let cache = EvaluationCache::new(&nlp.info);
+
+        cache.evaluate(&nlp, &[0.0], true).unwrap();
+
+        for x in 1..=2 * CAPACITY {
+            cache.evaluate(&nlp, &[x as f64], false).unwrap();
+        }
+
+        cache.evaluate(&nlp, &[0.0], false).unwrap();
+        assert_eq!(cache.counts().cache_hits, 1);
+    }
+
+    #[test]
+    fn points_beyond_the_evaluation_limit_are_rejected() {
+        let nlp = Parabola {
+            info: NlpInfo {
+                num_variables: 1,
+                num_inequality_constraints: 0,
+                num_equality_constraints: 0,
+            },
+        };
+        let cache = EvaluationCache::new(&nlp.info).with_max_evaluations(2);
+
+        cache.evaluate(&nlp, &[1.0], true).unwrap();
+        cache.evaluate(&nlp, &[2.0], false).unwrap();
+        assert!(!cache.exhausted());
+
+        assert!(cache.evaluate(&nlp, &[3.0], false).is_err());
+        assert!(cache.exhausted());
+
+        assert!(cache.evaluate(&nlp, &[2.0], true).is_ok());
+        assert_eq!(cache.counts().objective, 2);
+    }
+
+    #[test]
+    fn reset_forgets_the_previous_solve() {
+        let nlp = Parabola {
+            info: NlpInfo {
+                num_variables: 1,
+                num_inequality_constraints: 0,
+                num_equality_constraints: 0,
+            },
+        };
+        let mut cache = EvaluationCache::new(&nlp.info).with_max_evaluations(1);
+
+        cache.evaluate(&nlp, &[1.0], true).unwrap();
+        assert!(cache.evaluate(&nlp, &[2.0], false).is_err());
+
+        cache.reset();
+
+        assert!(!cache.exhausted());
+        assert_eq!(cache.counts(), Default::default());
+        assert_eq!(cache.callback_time(), Duration::ZERO);
+        assert!(cache.evaluate(&nlp, &[2.0], false).is_ok());
+        assert!(cache.evaluate(&nlp, &[1.0], false).is_err());
+    }
+}
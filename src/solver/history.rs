@@ -0,0 +1,149 @@
+use std::mem::size_of;
+
+use crate::optimizer::OptContext;
+use crate::options::History;
+
+/// Per-iteration record of a solve, see [`History`](crate::OptionsHistory).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvergenceHistory {
+    /// Penalized objective of every iteration.
+    pub objectives: Vec<f64>,
+    pub pure_objectives: Vec<f64>,
+    pub step_lengths: Vec<f64>,
+    pub constraint_violations: Vec<f64>,
+    /// Iterates paired with their iteration number.
+    pub iterates: Vec<(u32, Vec<f64>)>,
+}
+
+pub(crate) struct HistoryRecorder {
+    history: ConvergenceHistory,
+    iterate_stride: u32,
+    max_memory: usize,
+}
+
+impl HistoryRecorder {
+    pub fn new(options: &History) -> Option<Self> {
+        if !options.enabled {
+            return None;
+        }
+
+        Some(Self {
+            history: Default::default(),
+            iterate_stride: options.iterate_stride,
+            max_memory: options.max_memory,
+        })
+    }
+
+    pub fn record(&mut self, context: &OptContext) {
+        self.history.objectives.push(context.objective_current);
+        self.history.pure_objectives.push(context.pure_objective);
+        self.history
+            .step_lengths
+            .push(context.direction_scale_factor);
+        self.history
+            .constraint_violations
+            .push(context.optimality.constraint_violation);
+
+        if self.iterate_stride == 0 || context.iteration % self.iterate_stride != 0 {
+            return;
+        }
+
+        let iterate_size = context.x_current.len() * size_of::<f64>();
+
+        if iterate_size > self.max_memory {
+            return;
+        }
+
+        while (self.history.iterates.len() + 1) * iterate_size > self.max_memory {
+            self.iterate_stride = self.iterate_stride.saturating_mul(2);
+
+            let stride = self.iterate_stride;
+            self.history
+                .iterates
+                .retain(|(iteration, _)| iteration % stride == 0);
+
+            if context.iteration % stride != 0 {
+                return;
+            }
+        }
+
+        self.history
+            .iterates
+            .push((context.iteration, context.x_current.clone()));
+    }
+
+    pub fn into_history(self) -> ConvergenceHistory {
+        self.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn context(iteration: u32) -> OptContext {
+        OptContext {
+            iteration,
+            x_current: vec![iteration as f64; 2],
+            x_previous: vec![0.0; 2],
+            objective_current: 1.0 / iteration as f64,
+            objective_previous: 0.0,
+            pure_objective: 0.0,
+            objective_grad: vec![0.0; 2],
+            direction_scale_factor: 1.0,
+            optimality: Default::default(),
+            barrier_parameter: 0.0,
+            penalty_parameter: 0.0,
+            elapsed: Duration::from_secs(0),
+        }
+    }
+
+    #[test]
+    fn iterates_are_thinned_out_to_respect_memory_cap() {
+        let mut recorder = HistoryRecorder::new(&History {
+            enabled: true,
+            iterate_stride: 1,
+            max_memory: 4 * 2 * size_of::<f64>(),
+        })
+        .unwrap();
+
+        for iteration in 1..=10 {
+            recorder.record(&context(iteration));
+        }
+
+        let history = recorder.into_history();
+        let iterations: Vec<u32> = history.iterates.iter().map(|(i, _)| *i).collect();
+
+        assert_eq!(history.objectives.len(), 10);
+        assert_eq!(iterations, [4, 8]);
+        assert_eq!(history.iterates[1].1, [8.0, 8.0]);
+    }
+
+    #[test]
+    fn stride_saturates_instead_of_overflowing() {
+        let mut recorder = HistoryRecorder::new(&History {
+            enabled: true,
+            iterate_stride: 1 << 31,
+            max_memory: 2 * size_of::<f64>(),
+        })
+        .unwrap();
+
+        recorder.record(&context(0));
+        recorder.record(&context(1 << 31));
+
+        let iterations: Vec<u32> = recorder
+            .into_history()
+            .iterates
+            .iter()
+            .map(|(i, _)| *i)
+            .collect();
+
+        assert_eq!(iterations, [0]);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        assert!(HistoryRecorder::new(&Default::default()).is_none());
+    }
+}
@@ -1,148 +1,673 @@
+use std::cell::RefCell;
 use std::fmt;
+use std::time::Instant;
 
 pub use augmented_lagrangian_constraint_handler::AugmentedLagrangianConstraintHandler;
 pub use barrier_bounds_handler::BarrierBoundsHandler;
-
-use crate::optimizer::Optimizer;
-use crate::options::Options;
-use crate::output::SolverLogger;
-use crate::step_size_control::StepSizeControl;
-use crate::vec_utils::norm2_sqr;
-use crate::{ArmijoGoldsteinRule, Bfgs, StdoutLogger, NLP};
+use barrier_bounds_handler::BoundPush;
+use evaluation_cache::EvaluationCache;
+pub use evaluation_cache::EvaluationCounts;
+pub use history::ConvergenceHistory;
+use history::HistoryRecorder;
+pub use multipliers::Multipliers;
+pub use optimality::OptimalityMeasures;
+use penalized_objective::{PenalizedMerit, PenalizedObjective};
+pub use statistics::SolverStatistics;
+pub use termination::TerminationStatus;
+pub use warm_start::WarmStart;
+
+use crate::nlp_validation::validate_nlp_at;
+use crate::optimizer::{OptContext, Optimizer, StepDirection};
+use crate::options::{
+    BoundsStrategy, ConstraintsStrategy, History, Optimizer as OptionsOptimizer, Options,
+    OptionsValidationError, StepSizeRule, Termination,
+};
+use crate::output::{Frequency, SolverLogger};
+use crate::step_size_control::{FallbackChain, LineSearchError, StepSizeControl, StrongWolfeRule};
+use crate::{ArmijoGoldsteinRule, Bfgs, EvaluationError, StdoutLogger, SteepestDescent, NLP};
 
 mod augmented_lagrangian_constraint_handler;
 mod barrier_bounds_handler;
+mod evaluation_cache;
+mod history;
+mod multipliers;
+mod optimality;
+mod penalized_objective;
+mod statistics;
+mod termination;
+mod warm_start;
+
+/// What the solver should do after an [intermediate callback](Solver::with_callback) returned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CallbackAction {
+    Continue,
+    Stop,
+}
+
+type IntermediateCallback<'a> = Box<dyn FnMut(&OptContext) -> CallbackAction + 'a>;
 
 #[allow(dead_code)]
-pub struct Solver<'a, N, S, O, L>
+pub struct Solver<'a, N, S, O>
 where
     N: NLP,
     S: StepSizeControl,
     O: Optimizer<N>,
-    L: SolverLogger,
 {
     nlp: &'a N,
     step_size_control: S,
     optimizer: O,
     bounds_handler: BarrierBoundsHandler,
+    bound_push: Option<BoundPush>,
     constraints_handler: AugmentedLagrangianConstraintHandler,
-    logger: Vec<L>,
+    /// Barrier parameter every solve starts from.
+    initial_barrier_parameter: f64,
+    /// Penalty parameter every solve starts from.
+    initial_penalty_parameter: f64,
+    logger: Vec<Box<dyn SolverLogger + 'a>>,
+    evaluation_cache: EvaluationCache,
+    termination: Termination,
+    history: History,
+    warm_start: Option<WarmStart>,
+    callback: Option<IntermediateCallback<'a>>,
+    state: Option<SolveState>,
+}
+
+/// Everything a solve in progress carries from one iteration to the next.
+struct SolveState {
+    context: OptContext,
+    direction: StepDirection,
+    /// Buffer of the line search's merit function.
+    merit_gradient: Vec<f64>,
+    start: Instant,
+    /// Barrier parameter of the last completed iteration.
+    barrier_parameter: f64,
+    status: Option<TerminationStatus>,
+    history: Option<HistoryRecorder>,
+    statistics: SolverStatistics,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+/// Iterator over the iterations of a solve, see [`Solver::iterations`].
+pub struct Iterations<'s, 'a, N, S, O>
+where
+    N: NLP,
+    S: StepSizeControl,
+    O: Optimizer<N>,
+{
+    solver: &'s mut Solver<'a, N, S, O>,
 }
 
-impl<'a, N> Solver<'a, N, ArmijoGoldsteinRule, Bfgs, StdoutLogger>
+impl<N, S, O> Iterator for Iterations<'_, '_, N, S, O>
 where
     N: NLP,
+    S: StepSizeControl,
+    O: Optimizer<N>,
 {
+    type Item = OptContext;
+
+    fn next(&mut self) -> Option<OptContext> {
+        self.solver.step().cloned()
+    }
+}
+
+impl<'a, N> Solver<'a, N, Box<dyn StepSizeControl>, Box<dyn Optimizer<N>>>
+where
+    N: NLP,
+{
+    /// Builds the components selected in `options`.
+    ///
+    /// # Panics
+    ///
+    /// If the options are invalid, see [`Solver::try_new`].
     pub fn new(nlp: &'a N, options: Options) -> Self {
-        Self {
-            nlp: &nlp,
-            step_size_control: ArmijoGoldsteinRule::new(
-                options.step_size_control.alpha_0,
-                options.step_size_control.tau,
-                options.step_size_control.c,
-            ),
-            optimizer: Bfgs::new(nlp),
-            bounds_handler: BarrierBoundsHandler {
+        Self::try_new(nlp, options).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [`Solver::new`], but returns the invalid options instead of panicking.
+    pub fn try_new(nlp: &'a N, options: Options) -> Result<Self, OptionsValidationError> {
+        options.validate()?;
+
+        let step = &options.step_size_control;
+        let armijo_goldstein = || ArmijoGoldsteinRule::new(step.alpha_0, step.tau, step.c);
+        let strong_wolfe = || StrongWolfeRule::new(step.alpha_0, step.c, step.c_curvature);
+        let step_size_control: Box<dyn StepSizeControl> = match step.rule {
+            StepSizeRule::ArmijoGoldstein => Box::new(armijo_goldstein()),
+            StepSizeRule::StrongWolfe => Box::new(strong_wolfe()),
+            StepSizeRule::StrongWolfeThenArmijoGoldstein => Box::new(FallbackChain::new(vec![
+                Box::new(strong_wolfe()),
+                Box::new(armijo_goldstein()),
+            ])),
+        };
+        let optimizer: Box<dyn Optimizer<N>> = match options.optimizer {
+            OptionsOptimizer::Bfgs => Box::new(Bfgs::new(nlp)),
+            OptionsOptimizer::SteepestDescent => Box::new(SteepestDescent {}),
+        };
+
+        Ok(Solver::with_valid_options(
+            nlp,
+            options,
+            step_size_control,
+            optimizer,
+        ))
+    }
+}
+
+impl<'a, N, S, O> Solver<'a, N, S, O>
+where
+    N: NLP,
+    S: StepSizeControl,
+    O: Optimizer<N>,
+{
+    /// Uses the given line search and optimizer instead of the ones selected in `options`; all
+    /// other options apply as in [`Solver::new`].
+    ///
+    /// # Panics
+    ///
+    /// If the options are invalid, see [`Solver::try_with_components`].
+    pub fn with_components(
+        nlp: &'a N,
+        options: Options,
+        step_size_control: S,
+        optimizer: O,
+    ) -> Self {
+        Self::try_with_components(nlp, options, step_size_control, optimizer)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [`Solver::with_components`], but returns the invalid options instead of panicking.
+    pub fn try_with_components(
+        nlp: &'a N,
+        options: Options,
+        step_size_control: S,
+        optimizer: O,
+    ) -> Result<Self, OptionsValidationError> {
+        options.validate()?;
+
+        Ok(Self::with_valid_options(
+            nlp,
+            options,
+            step_size_control,
+            optimizer,
+        ))
+    }
+
+    fn with_valid_options(
+        nlp: &'a N,
+        options: Options,
+        step_size_control: S,
+        optimizer: O,
+    ) -> Self {
+        let bounds_handler = match options.bounds_handler.strategy {
+            BoundsStrategy::Barrier => BarrierBoundsHandler {
                 bounds: nlp.bounds(),
                 barrier_parameter: options.bounds_handler.barrier_parameter,
                 barrier_decrease_factor: options.bounds_handler.barrier_decrease_factor,
             },
-            constraints_handler: AugmentedLagrangianConstraintHandler {
+        };
+        let bound_push = if options.bounds_handler.push_initial_point {
+            Some(BoundPush {
+                bound_push: options.bounds_handler.bound_push,
+                bound_frac: options.bounds_handler.bound_frac,
+            })
+        } else {
+            None
+        };
+        let constraints_handler = match options.constraints_handler.strategy {
+            ConstraintsStrategy::AugmentedLagrangian => AugmentedLagrangianConstraintHandler {
                 mu: vec![0.0; nlp.info().num_inequality_constraints as usize],
                 lambda: vec![0.0; nlp.info().num_equality_constraints as usize],
                 c: options.constraints_handler.c,
             },
-            logger: vec![StdoutLogger::new(options.logger.frequency)],
+        };
+
+        Solver {
+            nlp,
+            step_size_control,
+            optimizer,
+            bounds_handler,
+            bound_push,
+            constraints_handler,
+            initial_barrier_parameter: options.bounds_handler.barrier_parameter,
+            initial_penalty_parameter: options.constraints_handler.c,
+            logger: vec![Box::new(
+                StdoutLogger::with_frequency(
+                    options
+                        .logger
+                        .schedule
+                        .unwrap_or(Frequency::EveryNthIteration(options.logger.frequency)),
+                )
+                .with_header_interval(options.logger.header_interval),
+            )],
+            evaluation_cache: EvaluationCache::new(nlp.info())
+                .with_max_evaluations(options.termination.max_evaluations),
+            termination: options.termination,
+            history: options.history,
+            warm_start: None,
+            callback: None,
+            state: None,
         }
     }
-}
 
-impl<N, S, O, L> Solver<'_, N, S, O, L>
-where
-    N: NLP,
-    S: StepSizeControl,
-    O: Optimizer<N>,
-    L: SolverLogger,
-{
-    #[allow(dead_code)]
+    /// Starts every following [`solve`](Self::solve) from `warm_start` instead of the NLP's
+    /// initial guess.
+    pub fn with_warm_start(mut self, warm_start: WarmStart) -> Self {
+        self.warm_start = Some(warm_start);
+        self
+    }
+
+    /// Attaches another logger next to the ones already present.
+    pub fn with_logger(mut self, logger: impl SolverLogger + 'a) -> Self {
+        self.logger.push(Box::new(logger));
+        self
+    }
+
+    /// Detaches all loggers, including the [`StdoutLogger`] set up by [`Solver::new`].
+    pub fn without_loggers(mut self) -> Self {
+        self.logger.clear();
+        self
+    }
+
+    /// Calls `callback` after every iteration; returning [`CallbackAction::Stop`] ends the solve
+    /// with [`TerminationStatus::UserInterrupted`].
+    pub fn with_callback(
+        mut self,
+        callback: impl FnMut(&OptContext) -> CallbackAction + 'a,
+    ) -> Self {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Runs the solver to completion.
     pub fn solve(&mut self) -> Solution {
-        let mut context =
-            self.optimizer
-                .initialize(&self.nlp, &self.bounds_handler, &self.constraints_handler);
-
-        while !self.optimizer.done(&context) {
-            context.objective_previous = context.objective_current;
-            context.iteration += 1;
-
-            let g = self.nlp.inequality_constraints(&context.x_current);
-            let h = self.nlp.equality_constraints(&context.x_current);
-            let grad_f = self.bounds_handler.adapted_objective_gradient(
-                &context.x_current,
-                &self.nlp.grad_objective(&context.x_current),
-            );
-
-            context.objective_grad = self.constraints_handler.adapted_objective_grad(
-                &grad_f,
-                &g,
-                &self
-                    .nlp
-                    .grad_inequality_constraints(&context.x_current)
-                    .iter()
-                    .map(|x| &x[..])
-                    .collect::<Vec<_>>(),
-                &h,
-                &self
-                    .nlp
-                    .grad_equality_constraints(&context.x_current)
-                    .iter()
-                    .map(|x| &x[..])
-                    .collect::<Vec<_>>(),
-            );
-
-            let d = self.optimizer.iterate(self.nlp, &mut context);
-            context.x_previous = context.x_current.clone();
-
-            if norm2_sqr(&d) < 1.0E-10 {
-                break;
-            }
+        while self.step().is_some() {}
 
-            let step_info = self.step_size_control.do_step(
-                |xs| {
-                    self.constraints_handler.adapted_objective_value(
-                        self.bounds_handler
-                            .adapted_objective_value(&xs, self.nlp.objective(xs)),
-                        &self.nlp.inequality_constraints(&xs),
-                        &self.nlp.equality_constraints(&xs),
-                    )
-                },
-                &mut context.x_current,
-                &context.objective_grad,
-                &d,
-            );
-
-            context.pure_objective = self.nlp.objective(&context.x_current);
-            context.objective_current = step_info.obj_value;
-            context.direction_scale_factor = step_info.direction_scale_factor;
-
-            self.bounds_handler.update_barrier_parameter();
-
-            let g = self.nlp.inequality_constraints(&context.x_current);
-            let h = self.nlp.equality_constraints(&context.x_current);
-            self.constraints_handler.update_multipliers(&g, &h);
-
-            for logger in self.logger.iter_mut() {
-                logger.log(&context, false);
+        self.finish()
+    }
+
+    /// Runs a single iteration, starting a new solve if none is in progress. Returns `None` once
+    /// the solve has terminated; [`finish`](Self::finish) then yields the [`Solution`].
+    pub fn step(&mut self) -> Option<&OptContext> {
+        let mut state = match self.state.take() {
+            Some(state) => state,
+            None => self.begin(),
+        };
+
+        if state.status.is_none() {
+            #[cfg(feature = "tracing")]
+            let _solve = state.span.clone().entered();
+
+            if let Err(status) = self.iterate(&mut state) {
+                state.status = Some(status);
             }
         }
 
+        let state = self.state.insert(state);
+
+        match state.status {
+            None => Some(&state.context),
+            Some(_) => None,
+        }
+    }
+
+    /// Iterates like [`step`](Self::step), yielding a snapshot of every iteration.
+    pub fn iterations(&mut self) -> Iterations<'_, 'a, N, S, O> {
+        Iterations { solver: self }
+    }
+
+    /// Ends the solve in progress and returns its result. A solve that has not terminated on its
+    /// own counts as interrupted by the user.
+    pub fn finish(&mut self) -> Solution {
+        let state = match self.state.take() {
+            Some(state) => state,
+            None => self.begin(),
+        };
+        #[cfg(feature = "tracing")]
+        let _solve = state.span.enter();
+        let context = state.context;
+        let callback_time = state.statistics.callback_time + self.evaluation_cache.callback_time();
+        let statistics = SolverStatistics {
+            evaluations: self.evaluation_cache.counts(),
+            optimizer_resets: self.optimizer.num_resets(),
+            solver_time: state.start.elapsed() - callback_time,
+            callback_time,
+            ..state.statistics
+        };
+
         for logger in self.logger.iter_mut() {
             logger.log(&context, true);
         }
 
-        Solution {
+        let multipliers = Multipliers::new(
+            &context.x_current,
+            state.barrier_parameter,
+            &self.bounds_handler,
+            &self.constraints_handler,
+        );
+
+        let solution = Solution {
             best_objective_value: context.pure_objective,
             best_solution: context.x_current,
             num_iterations: context.iteration,
+            status: state.status.unwrap_or(TerminationStatus::UserInterrupted),
+            statistics,
+            optimality: context.optimality,
+            multipliers,
+            barrier_parameter: self.bounds_handler.barrier_parameter,
+            inverse_hessian: self.optimizer.inverse_hessian(),
+            history: state.history.map(HistoryRecorder::into_history),
+        };
+
+        for logger in self.logger.iter_mut() {
+            logger.finish(&solution);
+        }
+
+        solution
+    }
+
+    /// Overrides the barrier parameter used from the next iteration on. Outside of a solve, every
+    /// following solve starts from it.
+    pub fn set_barrier_parameter(&mut self, barrier_parameter: f64) {
+        self.bounds_handler.barrier_parameter = barrier_parameter;
+
+        if self.state.is_none() {
+            self.initial_barrier_parameter = barrier_parameter;
+        }
+    }
+
+    /// Overrides the augmented-Lagrangian penalty parameter used from the next iteration on.
+    /// Outside of a solve, every following solve starts from it.
+    pub fn set_penalty_parameter(&mut self, penalty_parameter: f64) {
+        self.constraints_handler.c = penalty_parameter;
+
+        if self.state.is_none() {
+            self.initial_penalty_parameter = penalty_parameter;
+        }
+    }
+
+    fn begin(&mut self) -> SolveState {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            target: "runolinop",
+            "solve",
+            num_variables = self.nlp.info().num_variables
+        );
+        #[cfg(feature = "tracing")]
+        let _solve = span.enter();
+
+        for logger in self.logger.iter_mut() {
+            logger.start(self.nlp.info());
+        }
+
+        let start = Instant::now();
+
+        // a previous solve leaves its evaluations, parameters and multipliers behind
+        self.evaluation_cache.reset();
+        self.bounds_handler.barrier_parameter = self.initial_barrier_parameter;
+        self.constraints_handler.c = self.initial_penalty_parameter;
+        self.constraints_handler.mu.fill(0.0);
+        self.constraints_handler.lambda.fill(0.0);
+
+        let mut context =
+            self.optimizer
+                .initialize(self.nlp, &self.bounds_handler, &self.constraints_handler);
+
+        if self.warm_start.is_none() {
+            self.bounds_handler.fill_omitted(&mut context.x_current);
+
+            if let Some(bound_push) = &self.bound_push {
+                bound_push.apply(&mut context.x_current, &self.bounds_handler.bounds);
+            }
+
+            context.x_previous.clone_from(&context.x_current);
+        }
+
+        let mut validation = match &self.warm_start {
+            Some(warm_start) => warm_start
+                .check_lengths(self.nlp.info())
+                .and_then(|()| validate_nlp_at(self.nlp, &warm_start.x)),
+            None => validate_nlp_at(self.nlp, &context.x_current),
+        };
+
+        if let (Ok(()), Some(warm_start)) = (&validation, &self.warm_start) {
+            context.x_current.copy_from_slice(&warm_start.x);
+            context.x_previous.copy_from_slice(&warm_start.x);
+            self.constraints_handler
+                .mu
+                .copy_from_slice(&warm_start.inequality_multipliers);
+            self.constraints_handler
+                .lambda
+                .copy_from_slice(&warm_start.equality_multipliers);
+
+            if let Some(barrier_parameter) = warm_start.barrier_parameter {
+                self.bounds_handler.barrier_parameter = barrier_parameter;
+            }
+
+            if let Some(inverse_hessian) = &warm_start.inverse_hessian {
+                self.optimizer.set_inverse_hessian(inverse_hessian);
+            }
+        }
+
+        // the lengths of the callback results are checked by evaluating the starting point, which
+        // the first iteration then finds in the cache; other evaluation errors are left to it
+        if validation.is_ok() {
+            match self
+                .evaluation_cache
+                .evaluate(self.nlp, &context.x_current, true)
+            {
+                Ok(evaluation) => {
+                    context.optimality = OptimalityMeasures::new(
+                        &context.x_current,
+                        &evaluation,
+                        &self.bounds_handler,
+                        &self.constraints_handler,
+                    );
+                }
+                Err(EvaluationError {
+                    inconsistency: Some(error),
+                    ..
+                }) => validation = Err(error),
+                Err(_) => {}
+            }
+        }
+
+        SolveState {
+            direction: vec![0.0; context.x_current.len()],
+            merit_gradient: vec![0.0; context.x_current.len()],
+            context,
+            start,
+            barrier_parameter: self.bounds_handler.barrier_parameter,
+            status: validation.err().map(TerminationStatus::InvalidNlp),
+            history: HistoryRecorder::new(&self.history),
+            statistics: Default::default(),
+            #[cfg(feature = "tracing")]
+            span: span.clone(),
+        }
+    }
+
+    fn iterate(&mut self, state: &mut SolveState) -> Result<(), TerminationStatus> {
+        let context = &mut state.context;
+        let d = &mut state.direction;
+        let constrained = self.nlp.info().num_inequality_constraints > 0
+            || self.nlp.info().num_equality_constraints > 0;
+
+        if constrained && self.termination.kkt_satisfied(&context.optimality) {
+            return Err(TerminationStatus::Converged);
+        }
+
+        if context.iteration > 0 && self.termination.objective_converged(context) {
+            return Err(self.converged_or_infeasible(&context.x_current, &context.optimality));
+        }
+
+        if let Some(status) = self.termination.limit_reached(
+            context,
+            &self.evaluation_cache.counts(),
+            state.start.elapsed(),
+        ) {
+            return Err(status);
+        }
+
+        self.penalized_objective()
+            .gradient(&context.x_current, &mut context.objective_grad)
+            .map_err(|error| self.evaluation_error(error))?;
+
+        if self
+            .termination
+            .gradient_converged(context, &self.bounds_handler.bounds)
+        {
+            return Err(self.converged_or_infeasible(&context.x_current, &context.optimality));
+        }
+
+        context.objective_previous = context.objective_current;
+        context.iteration += 1;
+
+        #[cfg(feature = "tracing")]
+        let _outer_iteration = tracing::debug_span!(
+            target: "runolinop",
+            "outer_iteration",
+            iteration = context.iteration
+        )
+        .entered();
+
+        self.optimizer.iterate(self.nlp, context, d);
+        context.x_previous.copy_from_slice(&context.x_current);
+
+        if self.termination.stalled(d) {
+            return Err(TerminationStatus::Stalled);
+        }
+
+        let merit = PenalizedMerit {
+            objective: PenalizedObjective {
+                nlp: self.nlp,
+                evaluation_cache: &self.evaluation_cache,
+                bounds_handler: &self.bounds_handler,
+                constraints_handler: &self.constraints_handler,
+            },
+            gradient: RefCell::new(&mut state.merit_gradient),
+        };
+
+        #[cfg(feature = "tracing")]
+        let line_search = tracing::debug_span!(target: "runolinop", "line_search").entered();
+
+        let step_info = match self
+            .step_size_control
+            .do_step(&merit, &mut context.x_current, d)
+        {
+            Ok(step_info) => step_info,
+            Err(error) => {
+                // all trial points of a failed line search were rejected
+                state.statistics.line_search_trials += error.trial_points();
+                state.statistics.backtracks += error.trial_points();
+
+                return Err(match error {
+                    LineSearchError::EvaluationError { error, .. } => self.evaluation_error(error),
+                    LineSearchError::NoSufficientDecrease { .. } => {
+                        TerminationStatus::LineSearchFailed
+                    }
+                });
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        drop(line_search);
+
+        state.statistics.line_search_trials += step_info.trial_points;
+        state.statistics.backtracks += step_info.trial_points - 1;
+
+        let evaluation = self
+            .evaluation_cache
+            .evaluate(self.nlp, &context.x_current, true)
+            .map_err(|error| self.evaluation_error(error))?;
+
+        context.pure_objective = evaluation.objective;
+        context.objective_current = step_info.obj_value;
+        context.direction_scale_factor = step_info.direction_scale_factor;
+
+        self.constraints_handler.update_multipliers(
+            &evaluation.inequality_constraints,
+            &evaluation.equality_constraints,
+        );
+        if constrained {
+            state.statistics.multiplier_updates += 1;
+        }
+        context.optimality = OptimalityMeasures::new(
+            &context.x_current,
+            &evaluation,
+            &self.bounds_handler,
+            &self.constraints_handler,
+        );
+        state.barrier_parameter = self.bounds_handler.barrier_parameter;
+        context.barrier_parameter = self.bounds_handler.barrier_parameter;
+        context.penalty_parameter = self.constraints_handler.c;
+        context.elapsed = state.start.elapsed();
+        self.bounds_handler.update_barrier_parameter();
+
+        drop(evaluation);
+
+        if let Some(history) = state.history.as_mut() {
+            history.record(context);
+        }
+
+        for logger in self.logger.iter_mut() {
+            logger.log(context, false);
+        }
+
+        if let Some(callback) = self.callback.as_mut() {
+            let callback_start = Instant::now();
+            let action = callback(context);
+            state.statistics.callback_time += callback_start.elapsed();
+
+            if action == CallbackAction::Stop {
+                return Err(TerminationStatus::UserInterrupted);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Status once the penalized objective stopped improving at `xs`. Constrained problems have
+    /// only converged if `optimality` satisfies the KKT conditions; at a feasible point that does
+    /// not, the multipliers are stuck and the solve stalled.
+    fn converged_or_infeasible(
+        &self,
+        xs: &[f64],
+        optimality: &OptimalityMeasures,
+    ) -> TerminationStatus {
+        let constrained = self.nlp.info().num_inequality_constraints > 0
+            || self.nlp.info().num_equality_constraints > 0;
+
+        match self.evaluation_cache.evaluate(self.nlp, xs, false) {
+            Ok(evaluation)
+                if !self.termination.feasible(
+                    &evaluation.inequality_constraints,
+                    &evaluation.equality_constraints,
+                ) =>
+            {
+                TerminationStatus::Infeasible
+            }
+            Ok(_) if constrained && !self.termination.kkt_satisfied(optimality) => {
+                TerminationStatus::Stalled
+            }
+            Ok(_) => TerminationStatus::Converged,
+            Err(error) => self.evaluation_error(error),
+        }
+    }
+
+    /// Evaluation errors caused by the evaluation limit end the solve as such.
+    fn evaluation_error(&self, error: EvaluationError) -> TerminationStatus {
+        if self.evaluation_cache.exhausted() {
+            TerminationStatus::MaxEvaluations
+        } else {
+            TerminationStatus::EvaluationError(error)
+        }
+    }
+
+    fn penalized_objective(&self) -> PenalizedObjective<'_, N> {
+        PenalizedObjective {
+            nlp: self.nlp,
+            evaluation_cache: &self.evaluation_cache,
+            bounds_handler: &self.bounds_handler,
+            constraints_handler: &self.constraints_handler,
         }
     }
 }
@@ -152,13 +677,42 @@ pub struct Solution {
     pub best_objective_value: f64,
     pub best_solution: Vec<f64>,
     pub num_iterations: u32,
+    pub status: TerminationStatus,
+    pub statistics: SolverStatistics,
+    pub optimality: OptimalityMeasures,
+    pub multipliers: Multipliers,
+    /// Barrier parameter a continued solve would use next.
+    pub barrier_parameter: f64,
+    /// Row-major inverse Hessian approximation of the optimizer, if it keeps one.
+    pub inverse_hessian: Option<Vec<f64>>,
+    /// Recorded if enabled in the options.
+    pub history: Option<ConvergenceHistory>,
 }
 
 impl fmt::Display for Solution {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "best objective value: {}", self.best_objective_value)?;
-        writeln!(f, "best solution: {:?}", self.best_solution)?;
-        write!(f, "in {} iterations", self.num_iterations)
+        let rows = [
+            ("Objective", self.best_objective_value),
+            (
+                "Dual infeasibility",
+                self.optimality.lagrangian_gradient_norm,
+            ),
+            ("Constraint violation", self.optimality.constraint_violation),
+            ("Complementarity", self.optimality.complementarity),
+            ("Bound violation", self.optimality.bound_violation),
+        ];
+
+        writeln!(f, "Number of Iterations....: {}", self.num_iterations)?;
+        writeln!(f)?;
+
+        for (name, value) in rows.iter() {
+            writeln!(f, "{:.<24}: {:24.16e}", name, value)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{}", self.statistics)?;
+        writeln!(f)?;
+        write!(f, "EXIT: {}", self.status)
     }
 }
 
@@ -220,12 +774,21 @@ mod tests {
                 barrier_parameter: 1.0E-6,
                 barrier_decrease_factor: 0.5,
             },
+            bound_push: None,
             constraints_handler: AugmentedLagrangianConstraintHandler {
                 mu: vec![0.0; nlp.info().num_variables as usize],
                 lambda: vec![0.0; nlp.info.num_variables as usize],
                 c: 1.0,
             },
-            logger: vec![StdoutLogger::new(1)],
+            initial_barrier_parameter: 1.0E-6,
+            initial_penalty_parameter: 1.0,
+            logger: vec![Box::new(StdoutLogger::new(1))],
+            evaluation_cache: EvaluationCache::new(nlp.info()),
+            termination: Default::default(),
+            history: Default::default(),
+            warm_start: None,
+            callback: None,
+            state: None,
         };
 
         let solution = solver.solve();
@@ -283,12 +846,21 @@ mod tests {
                 barrier_parameter: 1.0E-6,
                 barrier_decrease_factor: 0.5,
             },
+            bound_push: None,
             constraints_handler: AugmentedLagrangianConstraintHandler {
                 mu: vec![0.0; nlp.info().num_variables as usize],
                 lambda: vec![0.0; nlp.info.num_variables as usize],
                 c: 1.0,
             },
-            logger: vec![StdoutLogger::new(1)],
+            initial_barrier_parameter: 1.0E-6,
+            initial_penalty_parameter: 1.0,
+            logger: vec![Box::new(StdoutLogger::new(1))],
+            evaluation_cache: EvaluationCache::new(nlp.info()),
+            termination: Default::default(),
+            history: Default::default(),
+            warm_start: None,
+            callback: None,
+            state: None,
         };
 
         let solution = solver.solve();
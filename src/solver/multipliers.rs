@@ -0,0 +1,83 @@
+use crate::{AugmentedLagrangianConstraintHandler, BarrierBoundsHandler};
+
+/// Lagrange multipliers of a solution.
+///
+/// They refer to the Lagrangian
+/// `f(x) + inequalityᵀ g(x) + equalityᵀ h(x) - lower_boundsᵀ (x - lb) - upper_boundsᵀ (ub - x)`,
+/// so that all multipliers except the equality ones are non-negative. The bound multipliers are
+/// the ones implied by the barrier terms and are zero for infinite bounds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Multipliers {
+    pub inequality: Vec<f64>,
+    pub equality: Vec<f64>,
+    pub lower_bounds: Vec<f64>,
+    pub upper_bounds: Vec<f64>,
+}
+
+impl Multipliers {
+    pub(crate) fn new(
+        xs: &[f64],
+        barrier_parameter: f64,
+        bounds_handler: &BarrierBoundsHandler,
+        constraints_handler: &AugmentedLagrangianConstraintHandler,
+    ) -> Self {
+        let bounds = xs.iter().zip(bounds_handler.bounds.iter());
+
+        Self {
+            inequality: constraints_handler.mu.clone(),
+            equality: constraints_handler.lambda.clone(),
+            lower_bounds: bounds
+                .clone()
+                .map(|(x, b)| bound_multiplier(barrier_parameter, x - b.lb))
+                .collect(),
+            upper_bounds: bounds
+                .map(|(x, b)| bound_multiplier(barrier_parameter, b.ub - x))
+                .collect(),
+        }
+    }
+}
+
+fn bound_multiplier(barrier_parameter: f64, distance: f64) -> f64 {
+    if distance.is_finite() {
+        barrier_parameter / distance
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VariableBounds;
+
+    #[test]
+    fn bound_multipliers_follow_from_barrier() {
+        let bounds_handler = BarrierBoundsHandler {
+            bounds: vec![
+                VariableBounds {
+                    lb: 1.0,
+                    ub: f64::INFINITY,
+                },
+                VariableBounds {
+                    lb: f64::NEG_INFINITY,
+                    ub: 2.0,
+                },
+            ],
+            barrier_parameter: 0.25,
+            barrier_decrease_factor: 0.5,
+        };
+        let constraints_handler = AugmentedLagrangianConstraintHandler {
+            mu: vec![3.0],
+            lambda: vec![-4.0],
+            c: 1.0,
+        };
+
+        let multipliers =
+            Multipliers::new(&[1.5, 1.75], 0.5, &bounds_handler, &constraints_handler);
+
+        assert_eq!(multipliers.inequality, [3.0]);
+        assert_eq!(multipliers.equality, [-4.0]);
+        assert_eq!(multipliers.lower_bounds, [1.0, 0.0]);
+        assert_eq!(multipliers.upper_bounds, [0.0, 2.0]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+
+use crate::solver::termination::max_constraint_violation;
+use crate::{AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, Evaluation};
+
+/// First-order (KKT) optimality and feasibility measures of an iterate.
+///
+/// The Lagrangian uses the current multiplier estimates of the constraint handler for `g` and
+/// `h`, and `barrier_parameter / (x - lb)` and `barrier_parameter / (ub - x)` for the bounds.
+/// All measures are infinite as long as the starting point could not be evaluated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptimalityMeasures {
+    /// Euclidean norm of the gradient of the Lagrangian.
+    pub lagrangian_gradient_norm: f64,
+    /// Largest violation of `g(x) <= 0` and `h(x) = 0`.
+    pub constraint_violation: f64,
+    /// Largest product of a multiplier with its constraint or bound distance.
+    pub complementarity: f64,
+    /// Largest distance of a variable outside its bounds.
+    pub bound_violation: f64,
+}
+
+impl Default for OptimalityMeasures {
+    fn default() -> Self {
+        Self {
+            lagrangian_gradient_norm: f64::INFINITY,
+            constraint_violation: f64::INFINITY,
+            complementarity: f64::INFINITY,
+            bound_violation: f64::INFINITY,
+        }
+    }
+}
+
+impl OptimalityMeasures {
+    /// Computes the measures at `xs`, which `evaluation` must hold including derivatives.
+    pub(crate) fn new(
+        xs: &[f64],
+        evaluation: &Evaluation,
+        bounds_handler: &BarrierBoundsHandler,
+        constraints_handler: &AugmentedLagrangianConstraintHandler,
+    ) -> Self {
+        let n = xs.len();
+        let g = &evaluation.inequality_constraints;
+        let h = &evaluation.equality_constraints;
+        let barrier_parameter = bounds_handler.barrier_parameter;
+
+        let mut lagrangian_gradient_norm_sqr = 0.0;
+        let mut complementarity: f64 = 0.0;
+        let mut bound_violation: f64 = 0.0;
+
+        for (i, (x, bounds)) in xs.iter().zip(bounds_handler.bounds.iter()).enumerate() {
+            let mut grad_i = evaluation.grad_objective[i];
+
+            for (mu_j, grad_g_j) in constraints_handler
+                .mu
+                .iter()
+                .zip(evaluation.grad_inequality_constraints.chunks(n.max(1)))
+            {
+                grad_i += mu_j * grad_g_j[i];
+            }
+
+            for (lambda_j, grad_h_j) in constraints_handler
+                .lambda
+                .iter()
+                .zip(evaluation.grad_equality_constraints.chunks(n.max(1)))
+            {
+                grad_i += lambda_j * grad_h_j[i];
+            }
+
+            if bounds.lb > f64::NEG_INFINITY {
+                grad_i -= barrier_parameter / (x - bounds.lb);
+                complementarity = complementarity.max(barrier_parameter);
+            }
+
+            if bounds.ub < f64::INFINITY {
+                grad_i += barrier_parameter / (bounds.ub - x);
+                complementarity = complementarity.max(barrier_parameter);
+            }
+
+            lagrangian_gradient_norm_sqr += grad_i.powi(2);
+            bound_violation = bound_violation.max(bounds.lb - x).max(x - bounds.ub);
+        }
+
+        for (mu_j, g_j) in constraints_handler.mu.iter().zip(g.iter()) {
+            complementarity = complementarity.max((mu_j * g_j).abs());
+        }
+
+        Self {
+            lagrangian_gradient_norm: lagrangian_gradient_norm_sqr.sqrt(),
+            constraint_violation: max_constraint_violation(g, h),
+            complementarity,
+            bound_violation,
+        }
+    }
+}
+
+impl fmt::Display for OptimalityMeasures {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "lagrangian gradient: {:e}, constraint violation: {:e}, complementarity: {:e}, bound violation: {:e}",
+            self.lagrangian_gradient_norm,
+            self.constraint_violation,
+            self.complementarity,
+            self.bound_violation
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NlpInfo, VariableBounds};
+
+    #[test]
+    fn measures_at_constrained_point() {
+        let mut evaluation = Evaluation::new(&NlpInfo {
+            num_variables: 2,
+            num_inequality_constraints: 1,
+            num_equality_constraints: 1,
+        });
+
+        // f = x0 + x1, g = -x0, h = x0 + x1 - 2 at x = (1.5, 0.5)
+        evaluation.objective = 2.0;
+        evaluation.grad_objective.copy_from_slice(&[1.0, 1.0]);
+        evaluation.inequality_constraints.copy_from_slice(&[-1.5]);
+        evaluation
+            .grad_inequality_constraints
+            .copy_from_slice(&[-1.0, 0.0]);
+        evaluation.equality_constraints.copy_from_slice(&[0.25]);
+        evaluation
+            .grad_equality_constraints
+            .copy_from_slice(&[1.0, 1.0]);
+
+        let bounds_handler = BarrierBoundsHandler {
+            bounds: vec![
+                VariableBounds {
+                    lb: f64::NEG_INFINITY,
+                    ub: f64::INFINITY,
+                },
+                VariableBounds { lb: 0.0, ub: 0.25 },
+            ],
+            barrier_parameter: 0.5,
+            barrier_decrease_factor: 0.5,
+        };
+        let constraints_handler = AugmentedLagrangianConstraintHandler {
+            mu: vec![0.5],
+            lambda: vec![-1.0],
+            c: 1.0,
+        };
+
+        let measures = OptimalityMeasures::new(
+            &[1.5, 0.5],
+            &evaluation,
+            &bounds_handler,
+            &constraints_handler,
+        );
+
+        // grad L = (1 - 0.5 - 1, 1 - 1 - 0.5 / 0.5 + 0.5 / -0.25) = (-0.5, -3)
+        assert_eq!(measures.lagrangian_gradient_norm, 9.25_f64.sqrt());
+        assert_eq!(measures.constraint_violation, 0.25);
+        assert_eq!(measures.complementarity, 0.75);
+        assert_eq!(measures.bound_violation, 0.25);
+    }
+}
@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+
+use crate::solver::evaluation_cache::EvaluationCache;
+use crate::step_size_control::MeritFunction;
+use crate::vec_utils::inner_product;
+use crate::{
+    AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, EvaluationError, NlpInfo, NLP,
+};
+
+/// The objective the optimizer actually minimizes: the NLP's objective with the barrier terms for
+/// the bounds and the augmented-Lagrangian terms for the constraints added.
+pub struct PenalizedObjective<'s, N: NLP> {
+    pub nlp: &'s N,
+    pub evaluation_cache: &'s EvaluationCache,
+    pub bounds_handler: &'s BarrierBoundsHandler,
+    pub constraints_handler: &'s AugmentedLagrangianConstraintHandler,
+}
+
+impl<N: NLP> PenalizedObjective<'_, N> {
+    pub fn value(&self, xs: &[f64]) -> Result<f64, EvaluationError> {
+        let evaluation = self.evaluation_cache.evaluate(self.nlp, xs, false)?;
+
+        Ok(self.constraints_handler.adapted_objective_value(
+            self.bounds_handler
+                .adapted_objective_value(xs, evaluation.objective),
+            &evaluation.inequality_constraints,
+            &evaluation.equality_constraints,
+        ))
+    }
+
+    pub fn gradient(&self, xs: &[f64], grad: &mut [f64]) -> Result<(), EvaluationError> {
+        let evaluation = self.evaluation_cache.evaluate(self.nlp, xs, true)?;
+        let n = num_variables(self.nlp.info());
+
+        grad.copy_from_slice(&evaluation.grad_objective);
+        self.bounds_handler.add_barrier_gradient(xs, grad);
+        self.constraints_handler.add_penalty_gradient(
+            grad,
+            &evaluation.inequality_constraints,
+            evaluation.grad_inequality_constraints.chunks(n),
+            &evaluation.equality_constraints,
+            evaluation.grad_equality_constraints.chunks(n),
+        );
+
+        Ok(())
+    }
+}
+
+/// The penalized objective as the line search sees it.
+pub struct PenalizedMerit<'s, N: NLP> {
+    pub objective: PenalizedObjective<'s, N>,
+    /// Buffer for the gradients behind directional derivatives.
+    pub gradient: RefCell<&'s mut [f64]>,
+}
+
+impl<N: NLP> MeritFunction for PenalizedMerit<'_, N> {
+    fn value(&self, x: &[f64]) -> Result<f64, EvaluationError> {
+        self.objective.value(x)
+    }
+
+    fn gradient(&self, x: &[f64], gradient: &mut [f64]) -> Result<bool, EvaluationError> {
+        self.objective.gradient(x, gradient).map(|_| true)
+    }
+
+    fn directional_derivative(&self, x: &[f64], direction: &[f64]) -> Result<f64, EvaluationError> {
+        let mut gradient = self.gradient.borrow_mut();
+        self.objective.gradient(x, &mut gradient)?;
+
+        Ok(inner_product(&gradient, direction).unwrap())
+    }
+
+    fn max_step(&self, x: &[f64], direction: &[f64]) -> f64 {
+        self.objective.bounds_handler.max_step(x, direction)
+    }
+}
+
+fn num_variables(info: &NlpInfo) -> usize {
+    (info.num_variables as usize).max(1)
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::EvaluationCounts;
+
+/// Work done during a solve.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolverStatistics {
+    pub evaluations: EvaluationCounts,
+    /// Trial points of all line searches, including the accepted ones.
+    pub line_search_trials: u32,
+    /// Trial points the line searches rejected.
+    pub backtracks: u32,
+    /// How often the optimizer discarded its curvature information, e.g. reset the BFGS matrix.
+    pub optimizer_resets: u32,
+    /// Updates of the constraint multipliers; zero without constraints.
+    pub multiplier_updates: u32,
+    /// Wall-clock time of the solve, excluding `callback_time`.
+    pub solver_time: Duration,
+    /// Wall-clock time spent in the NLP's callbacks and the intermediate callback.
+    pub callback_time: Duration,
+}
+
+impl fmt::Display for SolverStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: [(&str, u32); 9] = [
+            ("objective function evaluations", self.evaluations.objective),
+            ("objective gradient evaluations", self.evaluations.gradient),
+            ("constraint evaluations", self.evaluations.constraints),
+            (
+                "constraint Jacobian evaluations",
+                self.evaluations.constraint_gradients,
+            ),
+            ("evaluation cache hits", self.evaluations.cache_hits),
+            ("line search trial points", self.line_search_trials),
+            ("line search backtracks", self.backtracks),
+            ("optimizer resets", self.optimizer_resets),
+            ("multiplier updates", self.multiplier_updates),
+        ];
+
+        for (name, count) in rows.iter() {
+            writeln!(f, "{:<52} = {}", format!("Number of {}", name), count)?;
+        }
+
+        writeln!(
+            f,
+            "{:<52} = {:.3}",
+            "Total seconds in solver",
+            self.solver_time.as_secs_f64()
+        )?;
+        write!(
+            f,
+            "{:<52} = {:.3}",
+            "Total seconds in callbacks",
+            self.callback_time.as_secs_f64()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_aligns_values() {
+        let statistics = SolverStatistics {
+            line_search_trials: 12,
+            backtracks: 5,
+            solver_time: Duration::from_millis(1500),
+            ..Default::default()
+        };
+        let summary = statistics.to_string();
+        let lines: Vec<_> = summary.lines().collect();
+
+        assert_eq!(lines.len(), 11);
+        assert!(lines
+            .iter()
+            .all(|line| line.find('=') == lines[0].find('=')));
+        assert!(lines[5].starts_with("Number of line search trial points"));
+        assert!(lines[5].ends_with("= 12"));
+        assert!(lines[6].ends_with("= 5"));
+        assert!(lines[9].ends_with("= 1.500"));
+    }
+}
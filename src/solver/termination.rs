@@ -0,0 +1,147 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::optimizer::OptContext;
+use crate::options::Termination;
+use crate::solver::{EvaluationCounts, OptimalityMeasures};
+use crate::vec_utils::norm2;
+use crate::{EvaluationError, NlpValidationError, VariableBounds};
+
+/// Why [`Solver::solve`](crate::Solver::solve) stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TerminationStatus {
+    /// The convergence criteria are met at a feasible point, which satisfies the KKT conditions if
+    /// the problem is constrained.
+    Converged,
+    MaxIterations,
+    MaxEvaluations,
+    TimeLimit,
+    /// The search direction became too small to make any progress, or a constrained problem
+    /// stopped improving at a feasible point that does not satisfy the KKT conditions.
+    Stalled,
+    /// No step along the search direction decreased the penalized objective sufficiently.
+    LineSearchFailed,
+    /// The convergence criteria are met, but the constraints are violated.
+    Infeasible,
+    UserInterrupted,
+    EvaluationError(EvaluationError),
+    /// The NLP is inconsistent, see [`validate_nlp`](crate::validate_nlp), or the warm start does
+    /// not fit it; no iteration was run.
+    InvalidNlp(NlpValidationError),
+}
+
+impl fmt::Display for TerminationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TerminationStatus::Converged => write!(f, "converged"),
+            TerminationStatus::MaxIterations => write!(f, "maximum number of iterations reached"),
+            TerminationStatus::MaxEvaluations => {
+                write!(f, "maximum number of evaluations reached")
+            }
+            TerminationStatus::TimeLimit => write!(f, "time limit reached"),
+            TerminationStatus::Stalled => write!(f, "stalled"),
+            TerminationStatus::LineSearchFailed => write!(f, "line search failed"),
+            TerminationStatus::Infeasible => write!(f, "converged to an infeasible point"),
+            TerminationStatus::UserInterrupted => write!(f, "interrupted by user"),
+            TerminationStatus::EvaluationError(error) => write!(f, "{}", error),
+            TerminationStatus::InvalidNlp(error) => write!(f, "invalid NLP: {}", error),
+        }
+    }
+}
+
+impl Termination {
+    pub(crate) fn limit_reached(
+        &self,
+        context: &OptContext,
+        evaluations: &EvaluationCounts,
+        elapsed: Duration,
+    ) -> Option<TerminationStatus> {
+        if context.iteration >= self.max_iterations {
+            Some(TerminationStatus::MaxIterations)
+        } else if evaluations.objective >= self.max_evaluations {
+            Some(TerminationStatus::MaxEvaluations)
+        } else if elapsed.as_secs_f64() >= self.max_wall_time {
+            Some(TerminationStatus::TimeLimit)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn objective_converged(&self, context: &OptContext) -> bool {
+        let change = (context.objective_current - context.objective_previous).abs();
+
+        change < self.objective_tolerance
+            || change < self.relative_objective_tolerance * context.objective_current.abs()
+    }
+
+    pub(crate) fn gradient_converged(
+        &self,
+        context: &OptContext,
+        bounds: &[VariableBounds],
+    ) -> bool {
+        projected_gradient_norm(&context.x_current, &context.objective_grad, bounds)
+            < self.gradient_tolerance
+    }
+
+    pub(crate) fn stalled(&self, direction: &[f64]) -> bool {
+        norm2(direction) < self.step_tolerance
+    }
+
+    pub(crate) fn kkt_satisfied(&self, measures: &OptimalityMeasures) -> bool {
+        measures.lagrangian_gradient_norm < self.optimality_tolerance
+            && measures.complementarity < self.optimality_tolerance
+            && measures.constraint_violation <= self.constraint_tolerance
+            && measures.bound_violation <= self.constraint_tolerance
+    }
+
+    pub(crate) fn feasible(&self, g: &[f64], h: &[f64]) -> bool {
+        max_constraint_violation(g, h) <= self.constraint_tolerance
+    }
+}
+
+/// Norm of the step a projected gradient method would take, which vanishes at points that are
+/// stationary with respect to the bounds.
+pub fn projected_gradient_norm(xs: &[f64], grad: &[f64], bounds: &[VariableBounds]) -> f64 {
+    xs.iter()
+        .zip(grad.iter())
+        .zip(bounds.iter())
+        .map(|((x, g), b)| (x - (x - g).max(b.lb).min(b.ub)).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+pub fn max_constraint_violation(g: &[f64], h: &[f64]) -> f64 {
+    g.iter()
+        .map(|g_j| g_j.max(0.0))
+        .chain(h.iter().map(|h_j| h_j.abs()))
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projected_gradient_vanishes_at_active_bounds() {
+        let bounds = vec![VariableBounds { lb: 0.0, ub: 1.0 }; 2];
+
+        assert_eq!(
+            projected_gradient_norm(&[0.0, 1.0], &[2.0, -3.0], &bounds),
+            0.0
+        );
+        assert_eq!(
+            projected_gradient_norm(&[0.5, 0.5], &[0.3, -0.4], &bounds),
+            0.5
+        );
+        assert_eq!(
+            projected_gradient_norm(&[0.1, 0.5], &[2.0, 0.0], &bounds),
+            0.1
+        );
+    }
+
+    #[test]
+    fn constraint_violation() {
+        assert_eq!(max_constraint_violation(&[-1.0, 0.5], &[-0.7]), 0.7);
+        assert_eq!(max_constraint_violation(&[-1.0], &[]), 0.0);
+    }
+}
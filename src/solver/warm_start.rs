@@ -0,0 +1,66 @@
+use crate::solver::Solution;
+use crate::{NlpInfo, NlpValidationError};
+
+/// Starting information for a solve taken from a previous, related solve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WarmStart {
+    /// Replaces [`NLP::initial_guess`](crate::NLP::initial_guess).
+    pub x: Vec<f64>,
+    pub inequality_multipliers: Vec<f64>,
+    pub equality_multipliers: Vec<f64>,
+    /// Replaces the configured initial barrier parameter if given.
+    pub barrier_parameter: Option<f64>,
+    /// Row-major inverse Hessian approximation for optimizers that keep one.
+    pub inverse_hessian: Option<Vec<f64>>,
+}
+
+impl WarmStart {
+    /// Continues from where `solution` stopped, including its inverse Hessian approximation.
+    pub fn from_solution(solution: &Solution) -> Self {
+        Self {
+            x: solution.best_solution.clone(),
+            inequality_multipliers: solution.multipliers.inequality.clone(),
+            equality_multipliers: solution.multipliers.equality.clone(),
+            barrier_parameter: Some(solution.barrier_parameter),
+            inverse_hessian: solution.inverse_hessian.clone(),
+        }
+    }
+
+    /// Checks the length of every entry against `info`.
+    pub(crate) fn check_lengths(&self, info: &NlpInfo) -> Result<(), NlpValidationError> {
+        let n = info.num_variables as usize;
+        let expected_lengths = [
+            ("x", n, Some(self.x.len())),
+            (
+                "inequality_multipliers",
+                info.num_inequality_constraints as usize,
+                Some(self.inequality_multipliers.len()),
+            ),
+            (
+                "equality_multipliers",
+                info.num_equality_constraints as usize,
+                Some(self.equality_multipliers.len()),
+            ),
+            (
+                "inverse_hessian",
+                n * n,
+                self.inverse_hessian.as_ref().map(Vec::len),
+            ),
+        ];
+
+        for (field, expected, actual) in expected_lengths.iter() {
+            match actual {
+                Some(actual) if actual != expected => {
+                    return Err(NlpValidationError::WarmStartLengthMismatch {
+                        field,
+                        expected: *expected,
+                        actual: *actual,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::step_size_control::{
+    nan_error, not_nan, LineSearchError, MeritFunction, StepInfo, StepSizeControl,
+    MIN_RELATIVE_SCALE_FACTOR,
+};
+use crate::vec_utils::*;
+
+/// Backtracking from `alpha_0` by the factor `tau` until the decrease is at least `c` times the
+/// one predicted by the directional derivative.
+pub struct ArmijoGoldsteinRule {
+    alpha_0: f64,
+    tau: f64,
+    c: f64,
+    x_step: Vec<f64>,
+}
+
+impl ArmijoGoldsteinRule {
+    #[allow(dead_code)]
+    pub fn new(alpha_0: f64, tau: f64, c: f64) -> Self {
+        ArmijoGoldsteinRule {
+            alpha_0: alpha_0.max(1.0E-4),
+            tau: tau.clamp(1.0E-4, 1.0 - 1.0E-4),
+            c: c.clamp(1.0E-4, 1.0 - 1.0E-4),
+            x_step: vec![],
+        }
+    }
+}
+
+impl StepSizeControl for ArmijoGoldsteinRule {
+    fn do_step(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &mut [f64],
+        direction: &[f64],
+    ) -> Result<StepInfo, LineSearchError> {
+        let m = merit.directional_derivative(x, direction)?;
+        let t = -self.c * m;
+        let max_step = merit.max_step(x, direction);
+
+        let f_x = merit.value(x).and_then(not_nan)?;
+
+        let mut alpha_j = self.alpha_0;
+        let mut trial_points = 0;
+
+        self.x_step.resize(x.len(), 0.0);
+
+        // trial points that cannot be evaluated are rejected just like those without sufficient
+        // decrease; only if this persists down to a negligible step, the line search gives up
+        loop {
+            let negligible = alpha_j < MIN_RELATIVE_SCALE_FACTOR * self.alpha_0;
+
+            if alpha_j < max_step {
+                add_scaled_into(&mut self.x_step, x, direction, alpha_j);
+                trial_points += 1;
+
+                match merit.value(&self.x_step) {
+                    Ok(f_x_step) if f_x - f_x_step >= alpha_j * t => break,
+                    Ok(f_x_step) if negligible && f_x_step.is_nan() => {
+                        return Err(LineSearchError::EvaluationError {
+                            error: nan_error(),
+                            trial_points,
+                        })
+                    }
+                    Ok(_) if negligible => {
+                        return Err(LineSearchError::NoSufficientDecrease { trial_points })
+                    }
+                    Err(error) if negligible => {
+                        return Err(LineSearchError::EvaluationError {
+                            error,
+                            trial_points,
+                        })
+                    }
+                    _ => {}
+                }
+            } else if negligible {
+                return Err(LineSearchError::NoSufficientDecrease { trial_points });
+            }
+
+            alpha_j *= self.tau;
+        }
+
+        x.copy_from_slice(&self.x_step);
+
+        Ok(StepInfo {
+            obj_value: f_x,
+            direction_scale_factor: alpha_j,
+            trial_points,
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use crate::step_size_control::{LineSearchError, MeritFunction, StepInfo, StepSizeControl};
+
+/// Tries one line search after the other until one succeeds, e.g. [`StrongWolfeRule`] and
+/// [`ArmijoGoldsteinRule`] as a safeguard. Fails with the error of the last one. The trial points
+/// of the failed searches count toward the result.
+///
+/// [`StrongWolfeRule`]: crate::StrongWolfeRule
+/// [`ArmijoGoldsteinRule`]: crate::ArmijoGoldsteinRule
+pub struct FallbackChain {
+    rules: Vec<Box<dyn StepSizeControl>>,
+}
+
+impl FallbackChain {
+    pub fn new(rules: Vec<Box<dyn StepSizeControl>>) -> Self {
+        FallbackChain { rules }
+    }
+}
+
+impl StepSizeControl for FallbackChain {
+    fn do_step(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &mut [f64],
+        direction: &[f64],
+    ) -> Result<StepInfo, LineSearchError> {
+        let mut failed_trial_points = 0;
+        let mut error = LineSearchError::NoSufficientDecrease { trial_points: 0 };
+
+        for rule in self.rules.iter_mut() {
+            match rule.do_step(merit, x, direction) {
+                Ok(step_info) => {
+                    return Ok(StepInfo {
+                        trial_points: failed_trial_points + step_info.trial_points,
+                        ..step_info
+                    })
+                }
+                Err(rule_error) => {
+                    failed_trial_points += rule_error.trial_points();
+                    error = rule_error;
+                }
+            }
+        }
+
+        Err(match error {
+            LineSearchError::EvaluationError { error, .. } => LineSearchError::EvaluationError {
+                error,
+                trial_points: failed_trial_points,
+            },
+            LineSearchError::NoSufficientDecrease { .. } => LineSearchError::NoSufficientDecrease {
+                trial_points: failed_trial_points,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::step_size_control::tests::Parabola;
+    use crate::ArmijoGoldsteinRule;
+
+    /// Always fails after two trial points without touching `x`.
+    struct Failing;
+
+    impl StepSizeControl for Failing {
+        fn do_step(
+            &mut self,
+            _merit: &dyn MeritFunction,
+            _x: &mut [f64],
+            _direction: &[f64],
+        ) -> Result<StepInfo, LineSearchError> {
+            Err(LineSearchError::NoSufficientDecrease { trial_points: 2 })
+        }
+    }
+
+    #[test]
+    fn later_rules_take_over() {
+        let mut chain = FallbackChain::new(vec![
+            Box::new(Failing),
+            Box::new(ArmijoGoldsteinRule::new(1.0, 0.5, 0.1)),
+        ]);
+        let mut x = [0.0];
+
+        let step_info = chain.do_step(&Parabola, &mut x, &[1.0]).unwrap();
+
+        assert_eq!(x, [step_info.direction_scale_factor]);
+        // both trial points of `Failing` and the accepted `x = 1`
+        assert_eq!(step_info.trial_points, 3);
+    }
+
+    #[test]
+    fn fails_if_all_rules_fail() {
+        let mut chain = FallbackChain::new(vec![Box::new(Failing), Box::new(Failing)]);
+        let mut x = [0.0];
+
+        assert_eq!(
+            chain.do_step(&Parabola, &mut x, &[1.0]).err(),
+            Some(LineSearchError::NoSufficientDecrease { trial_points: 4 })
+        );
+        assert_eq!(x, [0.0]);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::EvaluationError;
+pub use armijo_goldstein::ArmijoGoldsteinRule;
+pub use fallback_chain::FallbackChain;
+pub use strong_wolfe::StrongWolfeRule;
+
+mod armijo_goldstein;
+mod fallback_chain;
+mod strong_wolfe;
+
+/// Function a line search decreases.
+pub trait MeritFunction {
+    fn value(&self, x: &[f64]) -> Result<f64, EvaluationError>;
+
+    /// Writes the gradient at `x` into `gradient`; returns `false` if there is none.
+    fn gradient(&self, _x: &[f64], _gradient: &mut [f64]) -> Result<bool, EvaluationError> {
+        Ok(false)
+    }
+
+    /// Derivative at `x` along `direction`.
+    fn directional_derivative(&self, x: &[f64], direction: &[f64]) -> Result<f64, EvaluationError>;
+
+    /// Multiple of `direction` at which `x` leaves the domain of the function; trial steps must
+    /// stay below it.
+    fn max_step(&self, _x: &[f64], _direction: &[f64]) -> f64 {
+        f64::INFINITY
+    }
+}
+
+/// Line search along a search direction.
+pub trait StepSizeControl {
+    /// Moves `x` along `direction` to a point with sufficient decrease of `merit`. On error, `x`
+    /// is left unchanged.
+    fn do_step(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &mut [f64],
+        direction: &[f64],
+    ) -> Result<StepInfo, LineSearchError>;
+}
+
+impl<S: StepSizeControl + ?Sized> StepSizeControl for Box<S> {
+    fn do_step(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &mut [f64],
+        direction: &[f64],
+    ) -> Result<StepInfo, LineSearchError> {
+        (**self).do_step(merit, x, direction)
+    }
+}
+
+/// Why a line search failed, together with the number of trial points it rejected before.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LineSearchError {
+    EvaluationError {
+        error: EvaluationError,
+        trial_points: u32,
+    },
+    NoSufficientDecrease {
+        trial_points: u32,
+    },
+}
+
+impl LineSearchError {
+    /// Number of trial points evaluated before the line search gave up.
+    pub fn trial_points(&self) -> u32 {
+        match self {
+            LineSearchError::EvaluationError { trial_points, .. }
+            | LineSearchError::NoSufficientDecrease { trial_points } => *trial_points,
+        }
+    }
+}
+
+/// An error before the first trial point.
+impl From<EvaluationError> for LineSearchError {
+    fn from(error: EvaluationError) -> Self {
+        LineSearchError::EvaluationError {
+            error,
+            trial_points: 0,
+        }
+    }
+}
+
+/// Outcome of a successful line search.
+pub struct StepInfo {
+    /// Value of the merit function at the point the line search started from.
+    pub obj_value: f64,
+    /// Multiple of the direction that was added to `x`.
+    pub direction_scale_factor: f64,
+    /// Number of trial points evaluated, including the accepted one.
+    pub trial_points: u32,
+}
+
+const MIN_RELATIVE_SCALE_FACTOR: f64 = 1.0E-12;
+
+fn nan_error() -> EvaluationError {
+    EvaluationError::new("objective value is NaN")
+}
+
+fn not_nan(value: f64) -> Result<f64, EvaluationError> {
+    if value.is_nan() {
+        Err(nan_error())
+    } else {
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(x - 3)^2` in one variable, defined for `x < 4` only.
+    pub struct Parabola;
+
+    impl MeritFunction for Parabola {
+        fn value(&self, x: &[f64]) -> Result<f64, EvaluationError> {
+            if x[0] < 4.0 {
+                Ok((x[0] - 3.0).powi(2))
+            } else {
+                Err(EvaluationError::new("outside the domain"))
+            }
+        }
+
+        fn directional_derivative(
+            &self,
+            x: &[f64],
+            direction: &[f64],
+        ) -> Result<f64, EvaluationError> {
+            Ok(2.0 * (x[0] - 3.0) * direction[0])
+        }
+
+        fn max_step(&self, x: &[f64], direction: &[f64]) -> f64 {
+            if direction[0] > 0.0 {
+                (4.0 - x[0]) / direction[0]
+            } else {
+                f64::INFINITY
+            }
+        }
+    }
+
+    #[test]
+    fn boxed_rules_are_interchangeable() {
+        let mut rules: Vec<Box<dyn StepSizeControl>> = vec![
+            Box::new(ArmijoGoldsteinRule::new(1.0, 0.5, 0.1)),
+            Box::new(StrongWolfeRule::new(1.0, 1.0E-4, 0.9)),
+        ];
+
+        for rule in rules.iter_mut() {
+            let mut x = [0.0];
+            let step_info = rule.do_step(&Parabola, &mut x, &[1.0]).unwrap();
+
+            assert!(x[0] > 0.0 && x[0] < 4.0);
+            assert_eq!(step_info.obj_value, 9.0);
+            assert_eq!(step_info.direction_scale_factor, x[0]);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use crate::step_size_control::{
+    not_nan, LineSearchError, MeritFunction, StepInfo, StepSizeControl, MIN_RELATIVE_SCALE_FACTOR,
+};
+use crate::vec_utils::*;
+
+const MAX_TRIAL_POINTS: u32 = 50;
+const EXPANSION_FACTOR: f64 = 2.0;
+/// Trial steps stay this fraction of the way to the boundary of the domain.
+const FRACTION_TO_BOUNDARY: f64 = 0.99;
+
+/// Line search for a step satisfying the strong Wolfe conditions: a decrease of at least `c1`
+/// times the one predicted by the directional derivative, and a directional derivative reduced
+/// in magnitude by at least the factor `c2` (Nocedal and Wright, algorithms 3.5 and 3.6).
+///
+/// Needs the directional derivative at every trial point. Trial points that cannot be evaluated
+/// are treated like ones with an infinite value.
+pub struct StrongWolfeRule {
+    alpha_0: f64,
+    c1: f64,
+    c2: f64,
+    x_step: Vec<f64>,
+}
+
+/// Value and directional derivative at a trial step.
+#[derive(Clone, Copy)]
+struct Trial {
+    alpha: f64,
+    value: f64,
+    slope: f64,
+}
+
+impl StrongWolfeRule {
+    pub fn new(alpha_0: f64, c1: f64, c2: f64) -> Self {
+        let c1 = c1.clamp(1.0E-4, 0.5);
+
+        StrongWolfeRule {
+            alpha_0: alpha_0.max(1.0E-4),
+            c1,
+            c2: c2.clamp(c1 + 1.0E-4, 1.0 - 1.0E-4),
+            x_step: vec![],
+        }
+    }
+
+    /// Evaluates the step `alpha`; points that cannot be evaluated count as infinitely bad.
+    fn trial(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &[f64],
+        direction: &[f64],
+        alpha: f64,
+    ) -> Trial {
+        add_scaled_into(&mut self.x_step, x, direction, alpha);
+
+        let value = merit.value(&self.x_step).and_then(not_nan);
+        let slope = merit.directional_derivative(&self.x_step, direction);
+
+        match (value, slope) {
+            (Ok(value), Ok(slope)) => Trial {
+                alpha,
+                value,
+                slope,
+            },
+            _ => Trial {
+                alpha,
+                value: f64::INFINITY,
+                slope: f64::NAN,
+            },
+        }
+    }
+
+    /// Returns the accepted step, or `None` if the search failed.
+    fn search(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &[f64],
+        direction: &[f64],
+        start: Trial,
+        trial_points: &mut u32,
+    ) -> Option<f64> {
+        let max_alpha = FRACTION_TO_BOUNDARY * merit.max_step(x, direction);
+        let (c1, c2) = (self.c1, self.c2);
+        let sufficient_decrease =
+            |trial: &Trial| trial.value <= start.value + c1 * trial.alpha * start.slope;
+        let curvature = |trial: &Trial| trial.slope.abs() <= -c2 * start.slope;
+
+        let mut previous = start;
+        let mut alpha = self.alpha_0.min(max_alpha);
+
+        // bracketing: expand the step until it is too long, then zoom in between the last two
+        let (mut lo, mut hi) = loop {
+            *trial_points += 1;
+
+            let trial = self.trial(merit, x, direction, alpha);
+
+            if !sufficient_decrease(&trial)
+                || (previous.alpha > 0.0 && trial.value >= previous.value)
+            {
+                break (previous, trial);
+            }
+
+            if curvature(&trial) {
+                return Some(trial.alpha);
+            }
+
+            if trial.slope >= 0.0 {
+                break (trial, previous);
+            }
+
+            let expanded = (EXPANSION_FACTOR * alpha).min(max_alpha);
+
+            if expanded <= alpha || *trial_points >= MAX_TRIAL_POINTS {
+                return Some(trial.alpha);
+            }
+
+            previous = trial;
+            alpha = expanded;
+        };
+
+        // zoom: `lo` satisfies sufficient decrease and is the best step so far, `hi` brackets
+        // a step satisfying both conditions together with `lo`
+        while *trial_points < MAX_TRIAL_POINTS
+            && (hi.alpha - lo.alpha).abs() >= MIN_RELATIVE_SCALE_FACTOR * self.alpha_0
+        {
+            *trial_points += 1;
+
+            let trial = self.trial(merit, x, direction, interpolate(&lo, &hi));
+
+            if !sufficient_decrease(&trial) || trial.value >= lo.value {
+                hi = trial;
+                continue;
+            }
+
+            if curvature(&trial) {
+                return Some(trial.alpha);
+            }
+
+            if trial.slope * (hi.alpha - lo.alpha) >= 0.0 {
+                hi = lo;
+            }
+
+            lo = trial;
+        }
+
+        // without a step satisfying the curvature condition, settle for sufficient decrease
+        if lo.alpha > 0.0 {
+            Some(lo.alpha)
+        } else {
+            None
+        }
+    }
+}
+
+/// Minimizer of the quadratic through the value and slope at `lo` and the value at `hi`,
+/// safeguarded to the inner 80% of the interval; bisection if `hi` has no finite value.
+fn interpolate(lo: &Trial, hi: &Trial) -> f64 {
+    let width = hi.alpha - lo.alpha;
+    let midpoint = lo.alpha + 0.5 * width;
+
+    if !hi.value.is_finite() {
+        return midpoint;
+    }
+
+    let curvature = (hi.value - lo.value - lo.slope * width) / width.powi(2);
+
+    if curvature <= 0.0 {
+        return midpoint;
+    }
+
+    let alpha = lo.alpha - lo.slope / (2.0 * curvature);
+    let (a, b) = if width > 0.0 {
+        (lo.alpha + 0.1 * width, hi.alpha - 0.1 * width)
+    } else {
+        (hi.alpha - 0.1 * width, lo.alpha + 0.1 * width)
+    };
+
+    alpha.clamp(a, b)
+}
+
+impl StepSizeControl for StrongWolfeRule {
+    fn do_step(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &mut [f64],
+        direction: &[f64],
+    ) -> Result<StepInfo, LineSearchError> {
+        let start = Trial {
+            alpha: 0.0,
+            value: merit.value(x).and_then(not_nan)?,
+            slope: merit.directional_derivative(x, direction)?,
+        };
+
+        if start.slope >= 0.0 {
+            return Err(LineSearchError::NoSufficientDecrease { trial_points: 0 });
+        }
+
+        self.x_step.resize(x.len(), 0.0);
+
+        let mut trial_points = 0;
+        let alpha = self
+            .search(merit, x, direction, start, &mut trial_points)
+            .ok_or(LineSearchError::NoSufficientDecrease { trial_points })?;
+
+        add_scaled_into(&mut self.x_step, x, direction, alpha);
+        x.copy_from_slice(&self.x_step);
+
+        Ok(StepInfo {
+            obj_value: start.value,
+            direction_scale_factor: alpha,
+            trial_points,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EvaluationError;
+
+    /// Rosenbrock's function in two variables.
+    struct Rosenbrock;
+
+    impl MeritFunction for Rosenbrock {
+        fn value(&self, x: &[f64]) -> Result<f64, EvaluationError> {
+            Ok((1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0].powi(2)).powi(2))
+        }
+
+        fn directional_derivative(
+            &self,
+            x: &[f64],
+            direction: &[f64],
+        ) -> Result<f64, EvaluationError> {
+            let grad = [
+                -2.0 * (1.0 - x[0]) - 400.0 * x[0] * (x[1] - x[0].powi(2)),
+                200.0 * (x[1] - x[0].powi(2)),
+            ];
+
+            Ok(grad[0] * direction[0] + grad[1] * direction[1])
+        }
+    }
+
+    #[test]
+    fn accepted_step_satisfies_strong_wolfe_conditions() {
+        let (c1, c2) = (1.0E-4, 0.1);
+        let mut rule = StrongWolfeRule::new(1.0, c1, c2);
+
+        for direction in [[1.0, 0.0], [0.01, 0.01], [2.0, 1.0]].iter() {
+            let x_start = [-1.2, 1.0];
+            let value = Rosenbrock.value(&x_start).unwrap();
+            let slope = Rosenbrock
+                .directional_derivative(&x_start, direction)
+                .unwrap();
+            let mut x = x_start;
+
+            let step_info = rule.do_step(&Rosenbrock, &mut x, direction).unwrap();
+            let alpha = step_info.direction_scale_factor;
+
+            assert!(Rosenbrock.value(&x).unwrap() <= value + c1 * alpha * slope);
+            assert!(
+                Rosenbrock
+                    .directional_derivative(&x, direction)
+                    .unwrap()
+                    .abs()
+                    <= -c2 * slope
+            );
+        }
+    }
+
+    #[test]
+    fn ascent_direction_is_rejected() {
+        let mut rule = StrongWolfeRule::new(1.0, 1.0E-4, 0.9);
+        let mut x = [-1.2, 1.0];
+
+        assert_eq!(
+            rule.do_step(&Rosenbrock, &mut x, &[-1.0, 0.0]).err(),
+            Some(LineSearchError::NoSufficientDecrease { trial_points: 0 })
+        );
+        assert_eq!(x, [-1.2, 1.0]);
+    }
+}
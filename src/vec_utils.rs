@@ -47,6 +47,14 @@ pub fn add(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
     }
 }
 
+/// Writes `a + s * b` into `out` without allocating.
+#[allow(dead_code)]
+pub fn add_scaled_into(out: &mut [f64], a: &[f64], b: &[f64], s: f64) {
+    for ((out_i, a_i), b_i) in out.iter_mut().zip(a.iter()).zip(b.iter()) {
+        *out_i = a_i + b_i * s;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,4 +90,12 @@ mod tests {
         assert_eq!(add(&a, &b).unwrap(), [4., 1., 3.]);
         assert_eq!(add(&[1., 2.], &[1., 2., 3.]), None);
     }
+
+    #[test]
+    fn add_scaled_into_test() {
+        let mut out = vec![0.; 3];
+
+        add_scaled_into(&mut out, &[1., 0., 2.], &[3., 1., 1.], 2.);
+        assert_eq!(out, [7., 2., 4.]);
+    }
 }
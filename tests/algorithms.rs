@@ -0,0 +1,87 @@
+use runolinop::{
+    ClosureNlp, NlpBuilder, Options, OptionsLogger, OptionsOptimizer, OptionsStepSizeControl,
+    Solver, StepSizeRule, TerminationStatus,
+};
+
+fn ill_conditioned_quadratic() -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2) + 10.0 * xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0], 20.0 * xs[1]])
+        .initial_guess(vec![1.0, 1.0])
+        .build()
+        .unwrap()
+}
+
+fn quiet() -> Options {
+    Options {
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn optimizer_is_selected_from_options() {
+    let nlp = ill_conditioned_quadratic();
+
+    let bfgs = Solver::new(&nlp, quiet()).solve();
+    let steepest_descent = Solver::new(
+        &nlp,
+        Options {
+            optimizer: OptionsOptimizer::SteepestDescent,
+            ..quiet()
+        },
+    )
+    .solve();
+
+    assert_eq!(bfgs.status, TerminationStatus::Converged);
+    assert!(bfgs.inverse_hessian.is_some());
+    assert!(steepest_descent.inverse_hessian.is_none());
+    assert!(steepest_descent.num_iterations > bfgs.num_iterations);
+    assert!(steepest_descent.best_objective_value < 1.0E-6);
+}
+
+#[test]
+fn line_search_is_selected_from_options() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (1.0 - xs[0]).powi(2) + 100.0 * (xs[1] - xs[0].powi(2)).powi(2))
+        .grad_objective(|xs| {
+            vec![
+                -2.0 * (1.0 - xs[0]) - 400.0 * xs[0] * (xs[1] - xs[0].powi(2)),
+                200.0 * (xs[1] - xs[0].powi(2)),
+            ]
+        })
+        .initial_guess(vec![-1.2, 1.0])
+        .build()
+        .unwrap();
+
+    for rule in [
+        StepSizeRule::ArmijoGoldstein,
+        StepSizeRule::StrongWolfe,
+        StepSizeRule::StrongWolfeThenArmijoGoldstein,
+    ]
+    .iter()
+    {
+        let solution = Solver::new(
+            &nlp,
+            Options {
+                step_size_control: OptionsStepSizeControl {
+                    rule: *rule,
+                    ..Default::default()
+                },
+                ..quiet()
+            },
+        )
+        .solve();
+
+        println!("{:?}:\n{}", rule, solution);
+        assert_eq!(solution.status, TerminationStatus::Converged, "{:?}", rule);
+        assert!(
+            (solution.best_solution[0] - 1.0).abs() < 1.0E-4,
+            "{:?}",
+            rule
+        );
+    }
+}
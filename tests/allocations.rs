@@ -0,0 +1,126 @@
+// Runs without the libtest harness, whose output capturing would allocate on every print.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use runolinop::{
+    EvaluationError, NlpInfo, Options, OptionsLogger, Solution, Solver, VariableBounds, NLP,
+};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+struct Rosenbrock {
+    info: NlpInfo,
+    initial_guess: [f64; 2],
+}
+
+impl NLP for Rosenbrock {
+    fn info(&self) -> &NlpInfo {
+        &self.info
+    }
+
+    fn bounds(&self) -> Vec<VariableBounds> {
+        vec![VariableBounds { lb: -5.0, ub: 5.0 }; 2]
+    }
+
+    fn objective(&self, xs: &[f64]) -> f64 {
+        100.0 * (xs[1] - xs[0] * xs[0]).powi(2) + (1.0 - xs[0]).powi(2)
+    }
+
+    fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+        let mut grad = vec![0.0; 2];
+        self.grad_objective_into(xs, &mut grad).unwrap();
+
+        grad
+    }
+
+    fn initial_guess(&self) -> Vec<f64> {
+        self.initial_guess.to_vec()
+    }
+
+    fn grad_objective_into(&self, xs: &[f64], grad: &mut [f64]) -> Result<(), EvaluationError> {
+        grad[0] = -400.0 * (xs[1] - xs[0] * xs[0]) * xs[0] - 2.0 * (1.0 - xs[0]);
+        grad[1] = 200.0 * (xs[1] - xs[0] * xs[0]);
+
+        Ok(())
+    }
+
+    fn equality_constraints_into(
+        &self,
+        _xs: &[f64],
+        _h: &mut [f64],
+    ) -> Result<(), EvaluationError> {
+        Ok(())
+    }
+
+    fn inequality_constraints_into(
+        &self,
+        _xs: &[f64],
+        _g: &mut [f64],
+    ) -> Result<(), EvaluationError> {
+        Ok(())
+    }
+}
+
+fn solve_counting_allocations(initial_guess: [f64; 2]) -> (Solution, usize) {
+    let nlp = Rosenbrock {
+        info: NlpInfo {
+            num_variables: 2,
+            num_inequality_constraints: 0,
+            num_equality_constraints: 0,
+        },
+        initial_guess,
+    };
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+
+    let solution = Solver::new(
+        &nlp,
+        Options {
+            logger: OptionsLogger {
+                frequency: u32::MAX,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .solve();
+
+    (solution, ALLOCATIONS.load(Ordering::SeqCst) - before)
+}
+
+fn main() {
+    // warm up the lazily allocated stdout buffer
+    solve_counting_allocations([0.5, 0.5]);
+
+    let (short_run, short_run_allocations) = solve_counting_allocations([0.5, 0.5]);
+    let (long_run, long_run_allocations) = solve_counting_allocations([-1.2, 1.0]);
+
+    assert!(long_run.num_iterations > short_run.num_iterations);
+    assert_eq!(
+        short_run_allocations, long_run_allocations,
+        "allocations grow with the number of iterations ({} vs. {})",
+        short_run.num_iterations, long_run.num_iterations
+    );
+
+    println!(
+        "{} allocations for both {} and {} iterations",
+        long_run_allocations, short_run.num_iterations, long_run.num_iterations
+    );
+}
@@ -0,0 +1,74 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use runolinop::{
+    CallbackAction, ClosureNlp, NlpBuilder, Options, OptionsLogger, Solver, TerminationStatus,
+};
+
+fn rosenbrock() -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(|xs| (1.0 - xs[0]).powi(2) + 100.0 * (xs[1] - xs[0].powi(2)).powi(2))
+        .grad_objective(|xs| {
+            vec![
+                -2.0 * (1.0 - xs[0]) - 400.0 * xs[0] * (xs[1] - xs[0].powi(2)),
+                200.0 * (xs[1] - xs[0].powi(2)),
+            ]
+        })
+        .initial_guess(vec![-1.2, 1.0])
+        .build()
+        .unwrap()
+}
+
+fn quiet() -> Options {
+    Options {
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn callback_sees_every_iteration() {
+    let nlp = rosenbrock();
+    let mut objectives = vec![];
+
+    let solution = Solver::new(&nlp, quiet())
+        .with_callback(|context| {
+            objectives.push(context.pure_objective);
+            CallbackAction::Continue
+        })
+        .solve();
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert_eq!(objectives.len(), solution.num_iterations as usize);
+    assert_eq!(objectives.last(), Some(&solution.best_objective_value));
+}
+
+#[test]
+fn callback_can_stop_the_solve() {
+    let nlp = rosenbrock();
+    let cancelled = AtomicBool::new(false);
+    let best_objective = Cell::new(f64::INFINITY);
+
+    let solution = Solver::new(&nlp, quiet())
+        .with_callback(|context| {
+            best_objective.set(context.pure_objective);
+
+            if context.iteration == 3 {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+
+            if cancelled.load(Ordering::Relaxed) {
+                CallbackAction::Stop
+            } else {
+                CallbackAction::Continue
+            }
+        })
+        .solve();
+
+    assert_eq!(solution.status, TerminationStatus::UserInterrupted);
+    assert_eq!(solution.num_iterations, 3);
+    assert_eq!(solution.best_objective_value, best_objective.get());
+}
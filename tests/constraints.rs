@@ -1,5 +1,7 @@
 use runolinop::{
-    NlpInfo, Options, OptionsBoundsHandler, OptionsStepSizeControl, Solver, VariableBounds, NLP,
+    NlpBuilder, NlpInfo, Options, OptionsBoundsHandler, OptionsConstraintsHandler,
+    OptionsOptimizer, OptionsStepSizeControl, OptionsTermination, Solver, TerminationStatus,
+    VariableBounds, NLP,
 };
 
 fn f(xs: &[f64]) -> f64 {
@@ -58,6 +60,7 @@ fn upper_bounds_problem() {
                 alpha_0: 5.0,
                 tau: 0.95,
                 c: 0.1,
+                ..Default::default()
             },
             bounds_handler: OptionsBoundsHandler {
                 barrier_parameter: 1.0,
@@ -70,6 +73,15 @@ fn upper_bounds_problem() {
     let solution = solver.solve();
     println!("solution: {}", solution);
 
+    for (z_l, z_u) in solution
+        .multipliers
+        .lower_bounds
+        .iter()
+        .zip(solution.multipliers.upper_bounds.iter())
+    {
+        assert!(*z_l >= 0.0 && *z_u > *z_l);
+    }
+
     for best_x in solution.best_solution {
         assert!((best_x - 3.123).abs() < 1.0E-3);
     }
@@ -130,8 +142,10 @@ fn inequality_constrained_min_problem() {
     let solution = solver.solve();
     println!("solution: {}", solution);
 
+    // the augmented Lagrangian approaches the constraint from either side
     assert!(
-        nlp.inequality_constraints(&solution.best_solution)[0] <= 0.0,
+        nlp.inequality_constraints(&solution.best_solution)[0]
+            <= OptionsTermination::default().constraint_tolerance,
         "sum of variable values: {}",
         solution.best_solution.iter().sum::<f64>()
     );
@@ -206,4 +220,67 @@ fn equality_constrained_min_problem() {
         "sum of variable values: {}",
         solution.best_solution.iter().sum::<f64>()
     );
+    assert_eq!(
+        solution.optimality.constraint_violation,
+        nlp.equality_constraints(&solution.best_solution)[0].abs()
+    );
+    assert!((solution.multipliers.equality[0] + 0.5).abs() < 0.05);
+    assert!(solution
+        .multipliers
+        .lower_bounds
+        .iter()
+        .all(|z| *z < 1.0E-6));
+}
+
+#[test]
+fn kkt_conditions_end_constrained_solve() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 2.0).powi(2) + (xs[1] - 1.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 2.0), 2.0 * (xs[1] - 1.0)])
+        .initial_guess(vec![0.0, 0.0])
+        .inequality_constraint(|xs| xs[0] + xs[1] - 10.0, |_| vec![1.0, 1.0])
+        .build()
+        .unwrap();
+
+    let solution = Solver::new(&nlp, Default::default()).solve();
+    println!("solution: {}", solution);
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert!(solution.optimality.lagrangian_gradient_norm < 1.0E-6);
+    assert!(solution.optimality.constraint_violation <= 1.0E-6);
+    assert!((solution.best_solution[0] - 2.0).abs() < 1.0E-6);
+    assert!((solution.best_solution[1] - 1.0).abs() < 1.0E-6);
+}
+
+#[test]
+fn multipliers_of_two_active_inequalities() {
+    // minimizer (1, 0.5), where 2 (x - (2, 2)) + mu = 0 yields mu = (2, 3)
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 2.0).powi(2) + (xs[1] - 2.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 2.0), 2.0 * (xs[1] - 2.0)])
+        .initial_guess(vec![0.0, 0.0])
+        .inequality_constraint(|xs| xs[0] - 1.0, |_| vec![1.0, 0.0])
+        .inequality_constraint(|xs| xs[1] - 0.5, |_| vec![0.0, 1.0])
+        .build()
+        .unwrap();
+
+    let solution = Solver::new(
+        &nlp,
+        Options {
+            optimizer: OptionsOptimizer::SteepestDescent,
+            constraints_handler: OptionsConstraintsHandler {
+                c: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .solve();
+    println!("solution: {}", solution);
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert!((solution.best_solution[0] - 1.0).abs() < 1.0E-4);
+    assert!((solution.best_solution[1] - 0.5).abs() < 1.0E-4);
+    assert!((solution.multipliers.inequality[0] - 2.0).abs() < 1.0E-3);
+    assert!((solution.multipliers.inequality[1] - 3.0).abs() < 1.0E-3);
 }
@@ -0,0 +1,113 @@
+use std::cell::RefCell;
+
+use runolinop::{Evaluation, EvaluationError, NlpInfo, Solver, VariableBounds, NLP};
+
+struct Recorded {
+    info: NlpInfo,
+    combined_points: RefCell<Vec<Vec<f64>>>,
+    gradient_points: RefCell<Vec<Vec<f64>>>,
+}
+
+impl NLP for Recorded {
+    fn info(&self) -> &NlpInfo {
+        &self.info
+    }
+
+    fn bounds(&self) -> Vec<VariableBounds> {
+        vec![
+            VariableBounds {
+                lb: 0.0,
+                ub: f64::INFINITY,
+            };
+            2
+        ]
+    }
+
+    fn objective(&self, xs: &[f64]) -> f64 {
+        xs[0].powi(2) + xs[1].powi(2)
+    }
+
+    fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+        self.gradient_points.borrow_mut().push(xs.to_vec());
+
+        vec![2.0 * xs[0], 2.0 * xs[1]]
+    }
+
+    fn inequality_constraints(&self, xs: &[f64]) -> Vec<f64> {
+        vec![-xs[0] - xs[1] + 0.5]
+    }
+
+    fn grad_inequality_constraints(&self, _xs: &[f64]) -> Vec<Vec<f64>> {
+        vec![vec![-1.0, -1.0]]
+    }
+
+    fn initial_guess(&self) -> Vec<f64> {
+        vec![1.0, 1.0]
+    }
+
+    fn evaluate(
+        &self,
+        xs: &[f64],
+        with_derivatives: bool,
+        evaluation: &mut Evaluation,
+    ) -> Result<(), EvaluationError> {
+        self.combined_points.borrow_mut().push(xs.to_vec());
+
+        evaluation.objective = self.objective(xs);
+        evaluation.inequality_constraints[0] = self.inequality_constraints(xs)[0];
+
+        if with_derivatives {
+            evaluation
+                .grad_objective
+                .copy_from_slice(&self.grad_objective(xs));
+            evaluation
+                .grad_inequality_constraints
+                .copy_from_slice(&self.grad_inequality_constraints(xs)[0]);
+        }
+
+        Ok(())
+    }
+}
+
+fn has_duplicates(points: &[Vec<f64>]) -> bool {
+    points
+        .iter()
+        .enumerate()
+        .any(|(i, x)| points[..i].contains(x))
+}
+
+#[test]
+fn every_point_is_evaluated_once() {
+    let nlp = Recorded {
+        info: NlpInfo {
+            num_variables: 2,
+            num_inequality_constraints: 1,
+            num_equality_constraints: 0,
+        },
+        combined_points: RefCell::new(vec![]),
+        gradient_points: RefCell::new(vec![]),
+    };
+
+    let solution = Solver::new(&nlp, Default::default()).solve();
+    println!("solution: {}", solution);
+
+    let combined_points = nlp.combined_points.borrow();
+    let gradient_points = nlp.gradient_points.borrow();
+
+    assert!(!has_duplicates(&combined_points));
+    assert!(!has_duplicates(&gradient_points));
+
+    assert_eq!(
+        solution.statistics.evaluations.objective as usize,
+        combined_points.len()
+    );
+    assert_eq!(
+        solution.statistics.evaluations.constraints as usize,
+        combined_points.len()
+    );
+    assert_eq!(
+        solution.statistics.evaluations.gradient as usize,
+        gradient_points.len()
+    );
+    assert!(solution.statistics.evaluations.cache_hits > 0);
+}
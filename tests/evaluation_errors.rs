@@ -0,0 +1,263 @@
+use std::cell::Cell;
+
+use runolinop::{
+    EvaluationError, NlpInfo, Options, OptionsStepSizeControl, OptionsTermination, Solver,
+    TerminationStatus, VariableBounds, NLP,
+};
+
+struct XMinusLnX {
+    info: NlpInfo,
+    initial_guess: f64,
+}
+
+impl XMinusLnX {
+    fn new(initial_guess: f64) -> Self {
+        XMinusLnX {
+            info: NlpInfo {
+                num_variables: 1,
+                num_inequality_constraints: 0,
+                num_equality_constraints: 0,
+            },
+            initial_guess,
+        }
+    }
+}
+
+impl NLP for XMinusLnX {
+    fn info(&self) -> &NlpInfo {
+        &self.info
+    }
+
+    fn bounds(&self) -> Vec<VariableBounds> {
+        vec![VariableBounds {
+            lb: f64::NEG_INFINITY,
+            ub: f64::INFINITY,
+        }]
+    }
+
+    fn objective(&self, xs: &[f64]) -> f64 {
+        xs[0] - xs[0].ln()
+    }
+
+    fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+        vec![1.0 - 1.0 / xs[0]]
+    }
+
+    fn initial_guess(&self) -> Vec<f64> {
+        vec![self.initial_guess]
+    }
+
+    fn try_objective(&self, xs: &[f64]) -> Result<f64, EvaluationError> {
+        if xs[0] > 0.0 {
+            Ok(self.objective(xs))
+        } else {
+            Err(EvaluationError::new("logarithm of non-positive number"))
+        }
+    }
+}
+
+#[test]
+fn trial_points_outside_domain_are_rejected() {
+    let nlp = XMinusLnX::new(5.0);
+
+    let mut solver = Solver::new(
+        &nlp,
+        Options {
+            step_size_control: OptionsStepSizeControl {
+                alpha_0: 10.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let solution = solver.solve();
+    println!("solution: {}", solution);
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert!((solution.best_solution[0] - 1.0).abs() < 1.0E-6);
+}
+
+#[test]
+fn repeated_evaluation_errors_end_the_solve() {
+    struct NowhereDifferentiable(XMinusLnX);
+
+    impl NLP for NowhereDifferentiable {
+        fn info(&self) -> &NlpInfo {
+            self.0.info()
+        }
+
+        fn bounds(&self) -> Vec<VariableBounds> {
+            self.0.bounds()
+        }
+
+        fn objective(&self, xs: &[f64]) -> f64 {
+            self.0.objective(xs)
+        }
+
+        fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+            self.0.grad_objective(xs)
+        }
+
+        fn initial_guess(&self) -> Vec<f64> {
+            self.0.initial_guess()
+        }
+
+        fn try_grad_objective(&self, _xs: &[f64]) -> Result<Vec<f64>, EvaluationError> {
+            Err(EvaluationError::new("no gradient available"))
+        }
+    }
+
+    let nlp = NowhereDifferentiable(XMinusLnX::new(5.0));
+    let solution = Solver::new(&nlp, Default::default()).solve();
+
+    assert_eq!(
+        solution.status,
+        TerminationStatus::EvaluationError(EvaluationError::new("no gradient available"))
+    );
+    assert_eq!(solution.best_solution, [5.0]);
+}
+
+#[test]
+fn line_search_gives_up_on_persistent_errors() {
+    struct OnlyAtStart(XMinusLnX);
+
+    impl NLP for OnlyAtStart {
+        fn info(&self) -> &NlpInfo {
+            self.0.info()
+        }
+
+        fn bounds(&self) -> Vec<VariableBounds> {
+            self.0.bounds()
+        }
+
+        fn objective(&self, xs: &[f64]) -> f64 {
+            self.0.objective(xs)
+        }
+
+        fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+            self.0.grad_objective(xs)
+        }
+
+        fn initial_guess(&self) -> Vec<f64> {
+            self.0.initial_guess()
+        }
+
+        fn try_objective(&self, xs: &[f64]) -> Result<f64, EvaluationError> {
+            if xs == self.initial_guess().as_slice() {
+                Ok(self.objective(xs))
+            } else {
+                Err(EvaluationError::new("simulation diverged"))
+            }
+        }
+    }
+
+    let nlp = OnlyAtStart(XMinusLnX::new(5.0));
+    let solution = Solver::new(&nlp, Default::default()).solve();
+    let statistics = solution.statistics;
+
+    assert_eq!(
+        solution.status,
+        TerminationStatus::EvaluationError(EvaluationError::new("simulation diverged"))
+    );
+    assert_eq!(solution.best_solution, [5.0]);
+    assert!(statistics.line_search_trials > 1);
+    assert_eq!(statistics.backtracks, statistics.line_search_trials);
+    assert_eq!(
+        statistics.evaluations.objective,
+        statistics.line_search_trials + 1
+    );
+}
+
+#[test]
+fn wrong_gradient_fails_the_line_search() {
+    struct WrongGradient(XMinusLnX);
+
+    impl NLP for WrongGradient {
+        fn info(&self) -> &NlpInfo {
+            self.0.info()
+        }
+
+        fn bounds(&self) -> Vec<VariableBounds> {
+            self.0.bounds()
+        }
+
+        fn objective(&self, xs: &[f64]) -> f64 {
+            self.0.objective(xs)
+        }
+
+        fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+            self.0.grad_objective(xs).iter().map(|g| -g).collect()
+        }
+
+        fn initial_guess(&self) -> Vec<f64> {
+            self.0.initial_guess()
+        }
+    }
+
+    let nlp = WrongGradient(XMinusLnX::new(5.0));
+    let solution = Solver::new(&nlp, Default::default()).solve();
+
+    assert_eq!(solution.status, TerminationStatus::LineSearchFailed);
+    assert_eq!(solution.best_solution, [5.0]);
+}
+
+#[test]
+fn failed_evaluations_count_toward_the_limit() {
+    struct CountingFailures {
+        nlp: XMinusLnX,
+        calls: Cell<u32>,
+    }
+
+    impl NLP for CountingFailures {
+        fn info(&self) -> &NlpInfo {
+            self.nlp.info()
+        }
+
+        fn bounds(&self) -> Vec<VariableBounds> {
+            self.nlp.bounds()
+        }
+
+        fn objective(&self, xs: &[f64]) -> f64 {
+            self.nlp.objective(xs)
+        }
+
+        fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+            self.nlp.grad_objective(xs)
+        }
+
+        fn initial_guess(&self) -> Vec<f64> {
+            self.nlp.initial_guess()
+        }
+
+        fn try_objective(&self, xs: &[f64]) -> Result<f64, EvaluationError> {
+            self.calls.set(self.calls.get() + 1);
+
+            if xs == self.initial_guess().as_slice() {
+                Ok(self.objective(xs))
+            } else {
+                Err(EvaluationError::new("simulation diverged"))
+            }
+        }
+    }
+
+    let nlp = CountingFailures {
+        nlp: XMinusLnX::new(5.0),
+        calls: Cell::new(0),
+    };
+    let solution = Solver::new(
+        &nlp,
+        Options {
+            termination: OptionsTermination {
+                max_evaluations: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .solve();
+
+    assert_eq!(solution.status, TerminationStatus::MaxEvaluations);
+    assert_eq!(solution.statistics.evaluations.objective, 3);
+    assert_eq!(nlp.calls.get(), 3);
+}
@@ -0,0 +1,80 @@
+use runolinop::{
+    AugmentedLagrangianConstraintHandler, BarrierBoundsHandler, LineSearchError, MeritFunction,
+    NlpBuilder, OptContext, Optimizer, Options, OptionsLogger, Solver, StepDirection, StepInfo,
+    StepSizeControl, TerminationStatus, NLP,
+};
+
+/// Newton's method for objectives whose Hessian is a known multiple of the identity.
+struct ScaledGradient {
+    curvature: f64,
+}
+
+impl<N: NLP> Optimizer<N> for ScaledGradient {
+    fn initialize(
+        &mut self,
+        nlp: &N,
+        bounds_handler: &BarrierBoundsHandler,
+        constraint_handler: &AugmentedLagrangianConstraintHandler,
+    ) -> OptContext {
+        OptContext::new(nlp, bounds_handler, constraint_handler)
+    }
+
+    fn iterate(&mut self, _nlp: &N, context: &mut OptContext, direction: &mut StepDirection) {
+        for (d_i, grad_i) in direction.iter_mut().zip(context.objective_grad.iter()) {
+            *d_i = -grad_i / self.curvature;
+        }
+    }
+}
+
+/// Takes every step in full as long as it does not increase the objective.
+struct FullStep;
+
+impl StepSizeControl for FullStep {
+    fn do_step(
+        &mut self,
+        merit: &dyn MeritFunction,
+        x: &mut [f64],
+        direction: &[f64],
+    ) -> Result<StepInfo, LineSearchError> {
+        let f_x = merit.value(x)?;
+        let x_step: Vec<f64> = x.iter().zip(direction).map(|(x, d)| x + d).collect();
+
+        if merit.value(&x_step)? > f_x {
+            return Err(LineSearchError::NoSufficientDecrease { trial_points: 0 });
+        }
+
+        x.copy_from_slice(&x_step);
+
+        Ok(StepInfo {
+            obj_value: f_x,
+            direction_scale_factor: 1.0,
+            trial_points: 1,
+        })
+    }
+}
+
+#[test]
+fn custom_components_drive_the_solve() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 1.0).powi(2) + (xs[1] + 2.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 1.0), 2.0 * (xs[1] + 2.0)])
+        .initial_guess(vec![5.0, 5.0])
+        .build()
+        .unwrap();
+
+    let options = Options {
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let solution =
+        Solver::with_components(&nlp, options, FullStep, ScaledGradient { curvature: 2.0 }).solve();
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert!(solution.num_iterations <= 2);
+    assert_eq!(solution.best_solution, [1.0, -2.0]);
+    assert_eq!(solution.statistics.backtracks, 0);
+}
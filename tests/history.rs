@@ -0,0 +1,70 @@
+use runolinop::{NlpBuilder, Options, OptionsHistory, OptionsLogger, Solver};
+
+#[test]
+fn history_records_a_monotone_trajectory() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (1.0 - xs[0]).powi(2) + 100.0 * (xs[1] - xs[0].powi(2)).powi(2))
+        .grad_objective(|xs| {
+            vec![
+                -2.0 * (1.0 - xs[0]) - 400.0 * xs[0] * (xs[1] - xs[0].powi(2)),
+                200.0 * (xs[1] - xs[0].powi(2)),
+            ]
+        })
+        .initial_guess(vec![-1.2, 1.0])
+        .build()
+        .unwrap();
+
+    let solution = Solver::new(
+        &nlp,
+        Options {
+            logger: OptionsLogger {
+                frequency: u32::MAX,
+                ..Default::default()
+            },
+            history: OptionsHistory {
+                enabled: true,
+                iterate_stride: 5,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .solve();
+
+    let history = solution.history.unwrap();
+    let n = solution.num_iterations as usize;
+
+    assert_eq!(history.objectives.len(), n);
+    assert_eq!(history.pure_objectives.len(), n);
+    assert_eq!(history.step_lengths.len(), n);
+    assert_eq!(history.constraint_violations.len(), n);
+    assert_eq!(
+        history.pure_objectives[n - 1],
+        solution.best_objective_value
+    );
+    assert!(history
+        .pure_objectives
+        .windows(2)
+        .all(|pair| pair[1] <= pair[0]));
+
+    assert_eq!(history.iterates.len(), n / 5);
+    for (k, (iteration, x)) in history.iterates.iter().enumerate() {
+        assert_eq!(*iteration as usize, 5 * (k + 1));
+        assert_eq!(x.len(), 2);
+    }
+}
+
+#[test]
+fn history_is_off_by_default() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0]])
+        .initial_guess(vec![1.0])
+        .build()
+        .unwrap();
+
+    assert!(Solver::new(&nlp, Default::default())
+        .solve()
+        .history
+        .is_none());
+}
@@ -0,0 +1,72 @@
+use runolinop::{
+    ClosureNlp, NlpBuilder, NlpValidationError, Options, OptionsBoundsHandler, OptionsLogger,
+    Solver, TerminationStatus, VariableBounds,
+};
+
+/// `(x - 2)^2 + (y - 3)^2` with `0 <= x <= 1` and `y >= 1`.
+fn nlp(initial_guess: Vec<f64>) -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(|xs| (xs[0] - 2.0).powi(2) + (xs[1] - 3.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 2.0), 2.0 * (xs[1] - 3.0)])
+        .bounds(vec![
+            VariableBounds { lb: 0.0, ub: 1.0 },
+            VariableBounds {
+                lb: 1.0,
+                ub: f64::INFINITY,
+            },
+        ])
+        .initial_guess(initial_guess)
+        .build()
+        .unwrap()
+}
+
+fn options(push_initial_point: bool) -> Options {
+    Options {
+        bounds_handler: OptionsBoundsHandler {
+            push_initial_point,
+            ..Default::default()
+        },
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn assert_optimal(xs: &[f64]) {
+    assert!((xs[0] - 1.0).abs() < 1.0E-3);
+    assert!((xs[1] - 3.0).abs() < 1.0E-3);
+}
+
+#[test]
+fn initial_guess_on_bound_is_pushed_into_interior() {
+    let nlp = nlp(vec![1.0, 5.0]);
+
+    let rejected = Solver::new(&nlp, options(false)).solve();
+
+    assert_eq!(
+        rejected.status,
+        TerminationStatus::InvalidNlp(NlpValidationError::InitialGuessNotInterior {
+            variable: 0,
+            value: 1.0,
+            lb: 0.0,
+            ub: 1.0
+        })
+    );
+
+    let pushed = Solver::new(&nlp, options(true)).solve();
+
+    assert_eq!(pushed.status, TerminationStatus::Converged);
+    assert_optimal(&pushed.best_solution);
+}
+
+#[test]
+fn omitted_initial_guess_is_picked_within_bounds() {
+    let nlp = nlp(vec![f64::NAN, f64::NAN]);
+
+    let solution = Solver::new(&nlp, options(false)).solve();
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert_optimal(&solution.best_solution);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "log")]
+
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use runolinop::{LogLogger, NlpBuilder, Solver};
+
+struct Capture {
+    records: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == "runolinop"
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.records
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn iterations_go_through_log() {
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 1.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 1.0)])
+        .initial_guess(vec![3.0])
+        .build()
+        .unwrap();
+
+    let solution = Solver::new(&nlp, Default::default())
+        .without_loggers()
+        .with_logger(LogLogger::new(Level::Debug))
+        .solve();
+
+    let records = CAPTURE.records.lock().unwrap();
+    let iterations = records
+        .iter()
+        .filter(|(level, message)| *level == Level::Debug && message.starts_with("iteration "))
+        .count();
+
+    assert_eq!(iterations, solution.num_iterations as usize);
+    assert!(records[0].1.starts_with("solving "));
+    assert_eq!(records.last().unwrap().0, Level::Info);
+    assert!(records.last().unwrap().1.starts_with("converged after "));
+}
@@ -0,0 +1,230 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use runolinop::{
+    CallbackAction, ClosureNlp, CsvLogger, Frequency, JsonLinesLogger, NlpBuilder, NlpInfo,
+    OptContext, Options, OptionsLogger, OptionsTermination, Solution, Solver, SolverLogger,
+    TerminationStatus,
+};
+
+#[derive(Default)]
+struct Events {
+    started_with: Option<u32>,
+    iterations: Vec<u32>,
+    finished_with: Option<TerminationStatus>,
+}
+
+struct Recorder<'e> {
+    events: &'e RefCell<Events>,
+}
+
+impl SolverLogger for Recorder<'_> {
+    fn start(&mut self, info: &NlpInfo) {
+        self.events.borrow_mut().started_with = Some(info.num_variables);
+    }
+
+    fn log(&mut self, context: &OptContext, ignore_frequency: bool) {
+        if !ignore_frequency {
+            self.events.borrow_mut().iterations.push(context.iteration);
+        }
+    }
+
+    fn finish(&mut self, solution: &Solution) {
+        self.events.borrow_mut().finished_with = Some(solution.status.clone());
+    }
+}
+
+struct Counter<'c> {
+    count: &'c RefCell<u32>,
+}
+
+impl SolverLogger for Counter<'_> {
+    fn log(&mut self, _context: &OptContext, _ignore_frequency: bool) {
+        *self.count.borrow_mut() += 1;
+    }
+}
+
+fn shifted_paraboloid() -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(|xs| (xs[0] - 1.0).powi(2) + (xs[1] + 2.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 1.0), 2.0 * (xs[1] + 2.0)])
+        .initial_guess(vec![0.0, 0.0])
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn different_loggers_can_be_attached() {
+    let nlp = shifted_paraboloid();
+
+    let events = RefCell::new(Events::default());
+    let count = RefCell::new(0);
+
+    let solution = Solver::new(&nlp, Default::default())
+        .without_loggers()
+        .with_logger(Recorder { events: &events })
+        .with_logger(Counter { count: &count })
+        .solve();
+
+    let events = events.into_inner();
+    let iterations: Vec<u32> = (1..=solution.num_iterations).collect();
+
+    assert_eq!(events.started_with, Some(2));
+    assert_eq!(events.iterations, iterations);
+    assert_eq!(events.finished_with, Some(solution.status));
+    assert_eq!(count.into_inner(), solution.num_iterations + 1);
+}
+
+#[test]
+fn csv_history() {
+    let nlp = shifted_paraboloid();
+    let mut csv = vec![];
+
+    let solution = Solver::new(&nlp, Default::default())
+        .without_loggers()
+        .with_logger(CsvLogger::new(&mut csv))
+        .solve();
+
+    let csv = String::from_utf8(csv).unwrap();
+    let rows: Vec<Vec<&str>> = csv.lines().map(|row| row.split(',').collect()).collect();
+
+    assert_eq!(
+        rows[0],
+        [
+            "iteration",
+            "objective",
+            "pure_objective",
+            "objective_change",
+            "gradient_norm",
+            "step_length",
+            "barrier_parameter",
+            "penalty_parameter",
+            "constraint_violation",
+            "elapsed_time"
+        ]
+    );
+    assert_eq!(rows.len(), solution.num_iterations as usize + 1);
+
+    for (i, row) in rows[1..].iter().enumerate() {
+        assert_eq!(row.len(), 10);
+        assert_eq!(row[0], (i + 1).to_string());
+        assert_eq!(row[7], "1000000000");
+    }
+
+    let last = rows.last().unwrap();
+    assert_eq!(
+        last[2].parse::<f64>().unwrap(),
+        solution.best_objective_value
+    );
+}
+
+#[test]
+fn write_errors_are_kept() {
+    struct Full;
+
+    impl Write for Full {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::WriteZero, "disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let nlp = shifted_paraboloid();
+    let mut csv = CsvLogger::new(Full);
+
+    Solver::new(&nlp, Default::default())
+        .without_loggers()
+        .with_logger(&mut csv)
+        .solve();
+
+    assert_eq!(csv.error().unwrap().kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn file_loggers_can_be_reused() {
+    let nlp = shifted_paraboloid();
+    // the line search runs out of evaluations, so the last iteration is only logged at the end
+    let options = || Options {
+        termination: OptionsTermination {
+            max_evaluations: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (mut csv_rows, mut json_lines) = (vec![], vec![]);
+    let mut csv = CsvLogger::new(&mut csv_rows);
+    let mut json = JsonLinesLogger::new(&mut json_lines);
+
+    for _ in 0..2 {
+        let solution = Solver::new(&nlp, options())
+            .without_loggers()
+            .with_logger(&mut csv)
+            .with_logger(&mut json)
+            .solve();
+
+        assert_eq!(solution.status, TerminationStatus::MaxEvaluations);
+        assert_eq!(solution.num_iterations, 1);
+    }
+
+    drop((csv, json));
+    let csv = String::from_utf8(csv_rows).unwrap();
+    let json = String::from_utf8(json_lines).unwrap();
+
+    assert_eq!(csv.lines().filter(|row| row.starts_with("1,")).count(), 2);
+    assert_eq!(json.lines().count(), 2);
+}
+
+#[test]
+fn json_lines_history() {
+    let nlp = shifted_paraboloid();
+    let mut json = vec![];
+
+    let solution = Solver::new(&nlp, Default::default())
+        .without_loggers()
+        .with_logger(JsonLinesLogger::new(&mut json))
+        .solve();
+
+    let json = String::from_utf8(json).unwrap();
+    let lines: Vec<&str> = json.lines().collect();
+
+    assert_eq!(lines.len(), solution.num_iterations as usize);
+    assert!(lines[0].starts_with("{\"iteration\":1,\"objective\":"));
+    assert!(lines[0].contains(",\"objective_change\":null,"));
+
+    for line in lines {
+        assert!(line.ends_with('}'));
+        assert_eq!(line.matches(':').count(), 10);
+    }
+}
+
+#[test]
+fn time_based_logging_survives_a_second_solve() {
+    let nlp = shifted_paraboloid();
+
+    let mut solver = Solver::new(
+        &nlp,
+        Options {
+            logger: OptionsLogger {
+                schedule: Some(Frequency::Every(Duration::from_millis(1))),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .with_callback(|_| {
+        // the second solve starts at an elapsed time below the last output of the first
+        thread::sleep(Duration::from_millis(2));
+        CallbackAction::Continue
+    });
+
+    let first = solver.solve();
+    let second = solver.solve();
+
+    assert_eq!(first.status, TerminationStatus::Converged);
+    assert_eq!(second.status, TerminationStatus::Converged);
+}
@@ -0,0 +1,71 @@
+use runolinop::{sum, Model, Options, OptionsStepSizeControl, Solver, NLP};
+
+#[test]
+fn rosenbrock_model() {
+    let mut model = Model::new();
+
+    let xs = model.add_variables("x", 4, f64::NEG_INFINITY, f64::INFINITY, 0.0);
+
+    model.minimize(sum((0..xs.len() - 1).map(|i| {
+        100.0 * (xs[i + 1] - xs[i].powi(2)).powi(2) + (1.0 - xs[i]).powi(2)
+    })));
+
+    let nlp = model.compile();
+    let solution = Solver::new(&nlp, Default::default()).solve();
+    println!("solution: {}", solution);
+
+    for (name, value) in nlp.named_values(&solution) {
+        assert!((value - 1.0).abs() < 1.0E-6, "{} = {}", name, value);
+    }
+}
+
+#[test]
+fn constrained_model_by_name() {
+    let mut model = Model::new();
+
+    let x = model.add_variable("x", 0.0, f64::INFINITY, 1.0);
+    let y = model.add_variable("y", 0.0, f64::INFINITY, 1.0);
+
+    model.minimize(x.powi(2) + y.powi(2));
+    model.add_equality_constraint(x + y - 0.5);
+
+    let nlp = model.compile();
+    let solution = Solver::new(
+        &nlp,
+        Options {
+            step_size_control: OptionsStepSizeControl {
+                alpha_0: 100.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .solve();
+    println!("solution: {}", solution);
+
+    let x = nlp.value(&solution, "x").unwrap();
+    let y = nlp.value(&solution, "y").unwrap();
+
+    assert!(nlp.equality_constraints(&solution.best_solution)[0].abs() <= 1.0E-3);
+    assert!((x - y).abs() <= 1.0E-3);
+    assert_eq!(nlp.value(&solution, "z"), None);
+}
+
+#[test]
+fn solution_of_another_model_has_no_values() {
+    let mut model = Model::new();
+    let x = model.add_variable("x", f64::NEG_INFINITY, f64::INFINITY, 1.0);
+    model.minimize(x.powi(2));
+    let small = model.compile();
+
+    let mut model = Model::new();
+    model.add_variable("x", f64::NEG_INFINITY, f64::INFINITY, 1.0);
+    model.add_variable("y", f64::NEG_INFINITY, f64::INFINITY, 1.0);
+    let large = model.compile();
+
+    let solution = Solver::new(&small, Default::default()).solve();
+
+    assert!(small.value(&solution, "x").is_some());
+    assert_eq!(large.value(&solution, "y"), None);
+    assert!(large.named_values(&solution).is_empty());
+}
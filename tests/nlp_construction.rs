@@ -41,3 +41,151 @@ fn nlp_definition() {
 
     dump_nlp(&nlp);
 }
+
+#[test]
+fn nlp_from_closures() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0]])
+        .bounds(vec![VariableBounds {
+            lb: 1.1,
+            ub: f64::INFINITY,
+        }])
+        .initial_guess(vec![2.0])
+        .build()
+        .unwrap();
+
+    assert_eq!(nlp.info().num_variables, 1);
+    assert_eq!(nlp.info().num_inequality_constraints, 0);
+    assert_eq!(nlp.info().num_equality_constraints, 0);
+
+    let solution = Solver::new(&nlp, Default::default()).solve();
+
+    assert!((solution.best_solution[0] - 1.1).abs() < 1.0E-6);
+}
+
+#[test]
+fn constraints_added_one_at_a_time() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2) + xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0], 2.0 * xs[1]])
+        .equality_constraint(|xs| xs[0] + xs[1] - 0.5, |_| vec![1.0, 1.0])
+        .inequality_constraint(|xs| 0.1 - xs[0], |_| vec![-1.0, 0.0])
+        .initial_guess(vec![1.0, 1.0])
+        .build()
+        .unwrap();
+
+    assert_eq!(nlp.info().num_inequality_constraints, 1);
+    assert_eq!(nlp.info().num_equality_constraints, 1);
+
+    let solution = Solver::new(
+        &nlp,
+        Options {
+            step_size_control: OptionsStepSizeControl {
+                alpha_0: 100.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .solve();
+    println!("solution: {}", solution);
+
+    assert!(nlp.equality_constraints(&solution.best_solution)[0].abs() <= 1.0E-3);
+}
+
+#[test]
+fn inconsistent_nlp_is_rejected_before_solving() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2) + xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0], 2.0 * xs[1]])
+        .inequality_constraints(2, |xs| vec![0.1 - xs[0]], |_| vec![vec![-1.0, 0.0]])
+        .initial_guess(vec![1.0, 1.0])
+        .build()
+        .unwrap();
+
+    let error = NlpValidationError::ConstraintsLengthMismatch {
+        kind: ConstraintKind::Inequality,
+        expected: 2,
+        actual: 1,
+    };
+
+    assert_eq!(validate_nlp(&nlp), Err(error.clone()));
+
+    let solution = Solver::new(&nlp, Default::default()).solve();
+
+    assert_eq!(solution.status, TerminationStatus::InvalidNlp(error));
+    assert_eq!(solution.num_iterations, 0);
+    // only the starting point, whose results showed the inconsistency
+    assert_eq!(solution.statistics.evaluations.objective, 1);
+}
+
+#[test]
+fn nlp_with_only_allocation_free_constraints_is_valid() {
+    // min x0^2 + x1^2 s.t. 1 - x0 - x1 <= 0, implemented through the *_into methods only
+    struct IntoOnly {
+        info: NlpInfo,
+    }
+
+    impl NLP for IntoOnly {
+        fn info(&self) -> &NlpInfo {
+            &self.info
+        }
+
+        fn bounds(&self) -> Vec<VariableBounds> {
+            vec![
+                VariableBounds {
+                    lb: f64::NEG_INFINITY,
+                    ub: f64::INFINITY,
+                };
+                2
+            ]
+        }
+
+        fn objective(&self, xs: &[f64]) -> f64 {
+            xs[0].powi(2) + xs[1].powi(2)
+        }
+
+        fn grad_objective(&self, xs: &[f64]) -> Vec<f64> {
+            vec![2.0 * xs[0], 2.0 * xs[1]]
+        }
+
+        fn initial_guess(&self) -> Vec<f64> {
+            vec![2.0, 2.0]
+        }
+
+        fn inequality_constraints_into(
+            &self,
+            xs: &[f64],
+            g: &mut [f64],
+        ) -> Result<(), EvaluationError> {
+            g[0] = 1.0 - xs[0] - xs[1];
+            Ok(())
+        }
+
+        fn grad_inequality_constraints_into(
+            &self,
+            _xs: &[f64],
+            grad_g: &mut [f64],
+        ) -> Result<(), EvaluationError> {
+            grad_g.copy_from_slice(&[-1.0, -1.0]);
+            Ok(())
+        }
+    }
+
+    let nlp = IntoOnly {
+        info: NlpInfo {
+            num_variables: 2,
+            num_inequality_constraints: 1,
+            num_equality_constraints: 0,
+        },
+    };
+
+    assert_eq!(validate_nlp(&nlp), Ok(()));
+
+    let solution = Solver::new(&nlp, Default::default()).solve();
+
+    assert!(!matches!(solution.status, TerminationStatus::InvalidNlp(_)));
+    assert!((solution.best_solution[0] - 0.5).abs() < 1.0E-3);
+    assert!((solution.best_solution[1] - 0.5).abs() < 1.0E-3);
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "serde")]
+
+use std::fs;
+use std::time::Duration;
+
+use runolinop::{Frequency, Options, OptionsError, OptionsOptimizer, StepSizeRule};
+
+#[test]
+fn toml_and_json_give_the_same_options() {
+    let from_toml = Options::from_toml(
+        r#"
+        optimizer = "steepest_descent"
+
+        [step_size_control]
+        rule = "strong_wolfe_then_armijo_goldstein"
+        alpha_0 = 2.0
+
+        [logger]
+        schedule = { every = 5.0 }
+
+        [termination]
+        max_iterations = 100
+        max_wall_time = inf
+        "#,
+    )
+    .unwrap();
+    let from_json = Options::from_json(
+        r#"{
+            "optimizer": "steepest_descent",
+            "step_size_control": { "rule": "strong_wolfe_then_armijo_goldstein", "alpha_0": 2.0 },
+            "logger": { "schedule": { "every": 5 } },
+            "termination": { "max_iterations": 100 }
+        }"#,
+    )
+    .unwrap();
+
+    for options in [from_toml, from_json].iter() {
+        assert_eq!(options.optimizer, OptionsOptimizer::SteepestDescent);
+        assert_eq!(
+            options.step_size_control.rule,
+            StepSizeRule::StrongWolfeThenArmijoGoldstein
+        );
+        assert_eq!(options.step_size_control.alpha_0, 2.0);
+        assert_eq!(options.step_size_control.tau, 0.5);
+        assert_eq!(
+            options.logger.schedule,
+            Some(Frequency::Every(Duration::from_secs(5)))
+        );
+        assert_eq!(options.termination.max_iterations, 100);
+        assert_eq!(options.termination.max_wall_time, f64::INFINITY);
+    }
+}
+
+#[test]
+fn unknown_keys_are_errors() {
+    for text in [
+        "max_iterations = 100",
+        "[termination]\nmax_iteration = 100",
+        "[solver]\nfoo = 1",
+    ]
+    .iter()
+    {
+        assert!(
+            matches!(Options::from_toml(text), Err(OptionsError::Parse(_))),
+            "{}",
+            text
+        );
+    }
+
+    assert!(matches!(
+        Options::from_toml("optimizer = \"newton\""),
+        Err(OptionsError::Parse(_))
+    ));
+    assert!(matches!(
+        Options::from_toml("[termination]\nmax_iterations = \"many\""),
+        Err(OptionsError::Parse(_))
+    ));
+}
+
+#[test]
+fn out_of_range_values_are_errors() {
+    let error = Options::from_toml("[step_size_control]\ntau = 1.5").unwrap_err();
+
+    match &error {
+        OptionsError::Invalid(error) => {
+            assert_eq!(error.invalid_options.len(), 1);
+            assert_eq!(error.invalid_options[0].option, "step_size_control.tau");
+            assert_eq!(error.invalid_options[0].value, "1.5");
+        }
+        _ => panic!("unexpected error: {}", error),
+    }
+    assert_eq!(
+        error.to_string(),
+        "invalid options: step_size_control.tau = 1.5 is outside of [1e-4, 1 - 1e-4]"
+    );
+}
+
+#[test]
+fn options_are_loaded_from_files() {
+    let directory = std::env::temp_dir();
+    let toml_path = directory.join(format!("runolinop-{}.toml", std::process::id()));
+    let json_path = directory.join(format!("runolinop-{}.json", std::process::id()));
+
+    fs::write(&toml_path, "[history]\nenabled = true").unwrap();
+    fs::write(&json_path, r#"{ "history": { "enabled": true } }"#).unwrap();
+
+    let from_toml = Options::load(&toml_path);
+    let from_json = Options::load(&json_path);
+    let missing = Options::load(directory.join("runolinop-missing.toml"));
+
+    fs::remove_file(&toml_path).unwrap();
+    fs::remove_file(&json_path).unwrap();
+
+    assert!(from_toml.unwrap().history.enabled);
+    assert!(from_json.unwrap().history.enabled);
+    assert!(matches!(missing, Err(OptionsError::Io(_))));
+}
@@ -0,0 +1,86 @@
+use runolinop::{
+    ArmijoGoldsteinRule, NlpBuilder, Options, OptionsConstraintsHandler, OptionsLogger,
+    OptionsStepSizeControl, Solver, SteepestDescent,
+};
+
+fn invalid_options() -> Options {
+    Options {
+        step_size_control: OptionsStepSizeControl {
+            c: 0.0,
+            ..Default::default()
+        },
+        constraints_handler: OptionsConstraintsHandler {
+            c: -1.0,
+            ..Default::default()
+        },
+        logger: OptionsLogger {
+            frequency: 0,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn try_new_reports_invalid_options() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0]])
+        .initial_guess(vec![1.0])
+        .build()
+        .unwrap();
+
+    let error = match Solver::try_new(&nlp, invalid_options()) {
+        Ok(_) => panic!("invalid options accepted"),
+        Err(error) => error,
+    };
+
+    let invalid: Vec<_> = error
+        .invalid_options
+        .iter()
+        .map(|invalid_option| (invalid_option.option, invalid_option.value.as_str()))
+        .collect();
+
+    assert_eq!(
+        invalid,
+        [
+            ("step_size_control.c", "0"),
+            ("constraints_handler.c", "-1"),
+            ("logger.frequency", "0")
+        ]
+    );
+    assert!(Solver::try_new(&nlp, Default::default()).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "logger.frequency = 0 is outside of [1, 4294967295]")]
+fn new_rejects_invalid_options() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0]])
+        .initial_guess(vec![1.0])
+        .build()
+        .unwrap();
+
+    Solver::new(&nlp, invalid_options());
+}
+
+#[test]
+fn try_with_components_reports_invalid_options() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0]])
+        .initial_guess(vec![1.0])
+        .build()
+        .unwrap();
+    let components = || (ArmijoGoldsteinRule::new(1.0, 0.5, 0.2), SteepestDescent {});
+
+    let (step_size_control, optimizer) = components();
+    let result = Solver::try_with_components(&nlp, invalid_options(), step_size_control, optimizer);
+    assert_eq!(result.err().unwrap().invalid_options.len(), 3);
+
+    let (step_size_control, optimizer) = components();
+    let result =
+        Solver::try_with_components(&nlp, Default::default(), step_size_control, optimizer);
+    assert!(result.is_ok());
+}
@@ -0,0 +1,122 @@
+use std::thread;
+use std::time::Duration;
+
+use runolinop::{
+    CallbackAction, ClosureNlp, NlpBuilder, Options, OptionsLogger, Solver, TerminationStatus,
+};
+
+fn rosenbrock() -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(|xs| (1.0 - xs[0]).powi(2) + 100.0 * (xs[1] - xs[0].powi(2)).powi(2))
+        .grad_objective(|xs| {
+            vec![
+                -2.0 * (1.0 - xs[0]) - 400.0 * xs[0] * (xs[1] - xs[0].powi(2)),
+                200.0 * (xs[1] - xs[0].powi(2)),
+            ]
+        })
+        .initial_guess(vec![-1.2, 1.0])
+        .build()
+        .unwrap()
+}
+
+fn quiet() -> Options {
+    Options {
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn statistics_count_line_search_work() {
+    let nlp = rosenbrock();
+
+    let solution = Solver::new(&nlp, quiet()).solve();
+    let statistics = solution.statistics;
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert_eq!(
+        statistics.line_search_trials - statistics.backtracks,
+        solution.num_iterations
+    );
+    assert!(statistics.backtracks > 0);
+    assert_eq!(statistics.multiplier_updates, 0);
+    // the NLP's callbacks
+    assert!(statistics.callback_time > Duration::from_secs(0));
+    assert!(statistics.evaluations.objective >= statistics.line_search_trials);
+}
+
+#[test]
+fn statistics_separate_callback_time() {
+    let nlp = rosenbrock();
+
+    let solution = Solver::new(&nlp, quiet())
+        .with_callback(|_| {
+            thread::sleep(Duration::from_millis(2));
+            CallbackAction::Continue
+        })
+        .solve();
+    let statistics = solution.statistics;
+
+    assert!(statistics.callback_time >= Duration::from_millis(2) * solution.num_iterations);
+    assert!(statistics.solver_time < statistics.callback_time);
+}
+
+#[test]
+fn statistics_count_nlp_evaluations_as_callback_time() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| {
+            thread::sleep(Duration::from_millis(2));
+            (xs[0] - 1.0).powi(2)
+        })
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 1.0)])
+        .initial_guess(vec![0.0])
+        .build()
+        .unwrap();
+
+    let solution = Solver::new(&nlp, quiet()).solve();
+    let statistics = solution.statistics;
+
+    assert!(
+        statistics.callback_time >= Duration::from_millis(2) * statistics.evaluations.objective
+    );
+    assert!(statistics.solver_time < statistics.callback_time);
+}
+
+#[test]
+fn constrained_solve_counts_multiplier_updates() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 1.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 1.0)])
+        .initial_guess(vec![0.0])
+        .inequality_constraint(|xs| xs[0] - 2.0, |_| vec![1.0])
+        .build()
+        .unwrap();
+
+    let solution = Solver::new(&nlp, quiet()).solve();
+
+    assert_eq!(
+        solution.statistics.multiplier_updates,
+        solution.num_iterations
+    );
+}
+
+#[test]
+fn summary_reports_statistics_and_status() {
+    let nlp = rosenbrock();
+
+    let solution = Solver::new(&nlp, quiet()).solve();
+    let summary = solution.to_string();
+
+    assert!(summary.starts_with(&format!(
+        "Number of Iterations....: {}",
+        solution.num_iterations
+    )));
+    assert!(summary.contains(&format!(
+        "Number of line search backtracks                     = {}",
+        solution.statistics.backtracks
+    )));
+    assert!(summary.ends_with("EXIT: converged"));
+}
@@ -0,0 +1,145 @@
+use runolinop::{
+    ClosureNlp, NlpBuilder, Options, OptionsLogger, Solver, TerminationStatus, VariableBounds,
+};
+
+fn rosenbrock() -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(|xs| (1.0 - xs[0]).powi(2) + 100.0 * (xs[1] - xs[0].powi(2)).powi(2))
+        .grad_objective(|xs| {
+            vec![
+                -2.0 * (1.0 - xs[0]) - 400.0 * xs[0] * (xs[1] - xs[0].powi(2)),
+                200.0 * (xs[1] - xs[0].powi(2)),
+            ]
+        })
+        .initial_guess(vec![-1.2, 1.0])
+        .build()
+        .unwrap()
+}
+
+fn quiet() -> Options {
+    Options {
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn iterating_matches_solve() {
+    let nlp = rosenbrock();
+    let solution = Solver::new(&nlp, quiet()).solve();
+
+    let mut solver = Solver::new(&nlp, quiet());
+    let snapshots: Vec<_> = solver.iterations().collect();
+    let stepped = solver.finish();
+
+    assert_eq!(stepped.status, TerminationStatus::Converged);
+    assert_eq!(stepped.best_solution, solution.best_solution);
+    assert_eq!(snapshots.len(), solution.num_iterations as usize);
+
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        assert_eq!(snapshot.iteration, i as u32 + 1);
+    }
+
+    assert!(snapshots
+        .windows(2)
+        .all(|pair| pair[1].objective_current <= pair[0].objective_current));
+}
+
+#[test]
+fn solves_can_be_interleaved_and_abandoned() {
+    let nlp = rosenbrock();
+    let mut first = Solver::new(&nlp, quiet());
+    let mut second = Solver::new(&nlp, quiet());
+
+    for _ in 0..5 {
+        assert!(first.step().is_some());
+        assert!(second.step().is_some());
+    }
+
+    let first = first.finish();
+    let second = second.solve();
+
+    assert_eq!(first.status, TerminationStatus::UserInterrupted);
+    assert_eq!(first.num_iterations, 5);
+    assert_eq!(second.status, TerminationStatus::Converged);
+    assert!(second.num_iterations > 5);
+}
+
+#[test]
+fn parameters_can_be_changed_between_steps() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0]])
+        .bounds(vec![VariableBounds { lb: 1.0, ub: 3.0 }])
+        .initial_guess(vec![2.0])
+        .build()
+        .unwrap();
+
+    let mut solver = Solver::new(&nlp, quiet());
+
+    for _ in 0..3 {
+        solver.step();
+    }
+
+    solver.set_barrier_parameter(0.5);
+    let x = solver.step().unwrap().x_current[0];
+    let solution = solver.finish();
+
+    assert_eq!(solution.barrier_parameter, 0.25);
+    assert_eq!(solution.multipliers.lower_bounds, [0.5 / (x - 1.0)]);
+
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0]])
+        .initial_guess(vec![0.0])
+        .equality_constraint(|xs| xs[0] - 1.0, |_| vec![1.0])
+        .build()
+        .unwrap();
+
+    let mut default_penalty = Solver::new(&nlp, quiet());
+    let mut low_penalty = Solver::new(&nlp, quiet());
+    low_penalty.set_penalty_parameter(10.0);
+
+    assert!(
+        low_penalty.step().unwrap().objective_current
+            < default_penalty.step().unwrap().objective_current
+    );
+}
+
+#[test]
+fn solving_again_repeats_the_solve() {
+    // min x0^2 + x1^2 s.t. x0 + x1 = 1, x0 in [0, 2]
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2) + xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0], 2.0 * xs[1]])
+        .bounds(vec![
+            VariableBounds { lb: 0.0, ub: 2.0 },
+            VariableBounds {
+                lb: f64::NEG_INFINITY,
+                ub: f64::INFINITY,
+            },
+        ])
+        .equality_constraint(|xs| xs[0] + xs[1] - 1.0, |_| vec![1.0, 1.0])
+        .initial_guess(vec![1.5, 1.5])
+        .build()
+        .unwrap();
+
+    for max_evaluations in [u32::MAX, 5] {
+        let mut options = quiet();
+        options.termination.max_evaluations = max_evaluations;
+        let mut solver = Solver::new(&nlp, options);
+
+        let first = solver.solve();
+        let second = solver.solve();
+
+        assert_eq!(second.status, first.status);
+        assert_eq!(second.num_iterations, first.num_iterations);
+        assert_eq!(second.statistics.evaluations, first.statistics.evaluations);
+        assert_eq!(second.best_solution, first.best_solution);
+        assert_eq!(second.multipliers, first.multipliers);
+        assert_eq!(second.barrier_parameter, first.barrier_parameter);
+    }
+}
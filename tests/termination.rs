@@ -0,0 +1,126 @@
+use runolinop::{
+    ClosureNlp, NlpBuilder, Options, OptionsLogger, OptionsTermination, Solver, TerminationStatus,
+};
+
+fn rosenbrock() -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(|xs| (1.0 - xs[0]).powi(2) + 100.0 * (xs[1] - xs[0].powi(2)).powi(2))
+        .grad_objective(|xs| {
+            vec![
+                -2.0 * (1.0 - xs[0]) - 400.0 * xs[0] * (xs[1] - xs[0].powi(2)),
+                200.0 * (xs[1] - xs[0].powi(2)),
+            ]
+        })
+        .initial_guess(vec![-1.2, 1.0])
+        .build()
+        .unwrap()
+}
+
+fn options(termination: OptionsTermination) -> Options {
+    Options {
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        termination,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn iteration_limit() {
+    let nlp = rosenbrock();
+
+    let solution = Solver::new(
+        &nlp,
+        options(OptionsTermination {
+            max_iterations: 3,
+            ..Default::default()
+        }),
+    )
+    .solve();
+
+    assert_eq!(solution.status, TerminationStatus::MaxIterations);
+    assert_eq!(solution.num_iterations, 3);
+}
+
+#[test]
+fn evaluation_limit() {
+    let nlp = rosenbrock();
+
+    let solution = Solver::new(
+        &nlp,
+        options(OptionsTermination {
+            max_evaluations: 10,
+            ..Default::default()
+        }),
+    )
+    .solve();
+
+    assert_eq!(solution.status, TerminationStatus::MaxEvaluations);
+    assert_eq!(solution.statistics.evaluations.objective, 10);
+}
+
+#[test]
+fn time_limit() {
+    let nlp = rosenbrock();
+
+    let solution = Solver::new(
+        &nlp,
+        options(OptionsTermination {
+            max_wall_time: 0.0,
+            ..Default::default()
+        }),
+    )
+    .solve();
+
+    assert_eq!(solution.status, TerminationStatus::TimeLimit);
+    assert_eq!(solution.num_iterations, 0);
+    assert_eq!(solution.best_solution, [-1.2, 1.0]);
+}
+
+#[test]
+fn loose_tolerances_stop_early() {
+    let nlp = rosenbrock();
+
+    let tight = Solver::new(&nlp, options(Default::default())).solve();
+    let loose = Solver::new(
+        &nlp,
+        options(OptionsTermination {
+            gradient_tolerance: 1.0E-2,
+            ..Default::default()
+        }),
+    )
+    .solve();
+
+    assert_eq!(tight.status, TerminationStatus::Converged);
+    assert_eq!(loose.status, TerminationStatus::Converged);
+    assert!(loose.num_iterations < tight.num_iterations);
+    assert!((tight.best_solution[0] - 1.0).abs() < 1.0E-4);
+}
+
+#[test]
+fn constrained_problems_only_converge_at_kkt_points() {
+    // min x0^2 + x1^2 s.t. x0 + x1 = 0.5; the objective stops changing while the multiplier is
+    // still far from -0.5
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2) + xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0], 2.0 * xs[1]])
+        .initial_guess(vec![1.0, 1.0])
+        .equality_constraint(|xs| xs[0] + xs[1] - 0.5, |_| vec![1.0, 1.0])
+        .build()
+        .unwrap();
+
+    let solution = Solver::new(
+        &nlp,
+        options(OptionsTermination {
+            objective_tolerance: 1.0E-4,
+            ..Default::default()
+        }),
+    )
+    .solve();
+
+    assert_eq!(solution.status, TerminationStatus::Stalled);
+    assert!(solution.optimality.lagrangian_gradient_norm > 1.0);
+    assert!(solution.optimality.constraint_violation < 1.0E-6);
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use runolinop::{
+    NlpBuilder, Options, OptionsTermination, Solver, TerminationStatus, TracingLogger,
+};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Default)]
+struct Recorded {
+    span_names: Vec<&'static str>,
+    stack: Vec<&'static str>,
+    iteration_events: Vec<Vec<&'static str>>,
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Recorded>>);
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut recorded = self.0.lock().unwrap();
+        recorded.span_names.push(span.metadata().name());
+
+        Id::from_u64(recorded.span_names.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        if event.metadata().fields().field("iteration").is_some() {
+            let mut recorded = self.0.lock().unwrap();
+            let stack = recorded.stack.clone();
+            recorded.iteration_events.push(stack);
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        let mut recorded = self.0.lock().unwrap();
+        let name = recorded.span_names[span.into_u64() as usize - 1];
+        recorded.stack.push(name);
+    }
+
+    fn exit(&self, _span: &Id) {
+        self.0.lock().unwrap().stack.pop();
+    }
+}
+
+#[test]
+fn iterations_are_traced_inside_spans() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 1.0).powi(2) + xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 1.0), 2.0 * xs[1]])
+        .initial_guess(vec![3.0, 1.0])
+        .equality_constraint(|xs| xs[1], |_| vec![0.0, 1.0])
+        .build()
+        .unwrap();
+
+    let recorder = Recorder::default();
+
+    let solution = tracing::subscriber::with_default(recorder.clone(), || {
+        Solver::new(&nlp, Default::default())
+            .without_loggers()
+            .with_logger(TracingLogger::new())
+            .solve()
+    });
+
+    let recorded = recorder.0.lock().unwrap();
+    let count = |name| recorded.span_names.iter().filter(|n| **n == name).count();
+
+    assert_eq!(count("solve"), 1);
+    assert_eq!(count("outer_iteration"), solution.num_iterations as usize);
+    // a stalled iteration ends before its line search
+    let stalled = solution.status == TerminationStatus::Stalled;
+    assert_eq!(
+        count("line_search"),
+        solution.num_iterations as usize - stalled as usize
+    );
+    assert_eq!(
+        recorded.iteration_events.len(),
+        solution.num_iterations as usize
+    );
+
+    // the last iteration may only be logged once the solve has ended
+    let (last, others) = recorded.iteration_events.split_last().unwrap();
+
+    for stack in others {
+        assert_eq!(stack, &["solve", "outer_iteration"]);
+    }
+
+    assert_eq!(last[0], "solve");
+}
+
+#[test]
+fn logger_can_be_reused() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 1.0).powi(2) + (xs[1] + 2.0).powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 1.0), 2.0 * (xs[1] + 2.0)])
+        .initial_guess(vec![0.0, 0.0])
+        .build()
+        .unwrap();
+    // the line search runs out of evaluations, so the last iteration is only logged at the end
+    let options = || Options {
+        termination: OptionsTermination {
+            max_evaluations: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let recorder = Recorder::default();
+    let mut logger = TracingLogger::new();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        for _ in 0..2 {
+            let solution = Solver::new(&nlp, options())
+                .without_loggers()
+                .with_logger(&mut logger)
+                .solve();
+
+            assert_eq!(solution.status, TerminationStatus::MaxEvaluations);
+            assert_eq!(solution.num_iterations, 1);
+        }
+    });
+
+    assert_eq!(recorder.0.lock().unwrap().iteration_events.len(), 2);
+}
@@ -0,0 +1,159 @@
+use runolinop::{
+    ClosureNlp, NlpBuilder, NlpValidationError, Options, OptionsLogger, OptionsTermination, Solver,
+    TerminationStatus, WarmStart,
+};
+
+fn rosenbrock(a: f64) -> ClosureNlp<'static> {
+    NlpBuilder::new()
+        .objective(move |xs| (a - xs[0]).powi(2) + 100.0 * (xs[1] - xs[0].powi(2)).powi(2))
+        .grad_objective(move |xs| {
+            vec![
+                -2.0 * (a - xs[0]) - 400.0 * xs[0] * (xs[1] - xs[0].powi(2)),
+                200.0 * (xs[1] - xs[0].powi(2)),
+            ]
+        })
+        .initial_guess(vec![-1.2, 1.0])
+        .build()
+        .unwrap()
+}
+
+fn quiet() -> Options {
+    Options {
+        logger: OptionsLogger {
+            frequency: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn perturbed_problem_converges_faster() {
+    let nlp = rosenbrock(1.0);
+    let previous = Solver::new(&nlp, quiet()).solve();
+
+    let perturbed = rosenbrock(1.01);
+    let cold = Solver::new(&perturbed, quiet()).solve();
+    let warm = Solver::new(&perturbed, quiet())
+        .with_warm_start(WarmStart::from_solution(&previous))
+        .solve();
+
+    assert_eq!(cold.status, TerminationStatus::Converged);
+    assert_eq!(warm.status, TerminationStatus::Converged);
+    assert!(warm.num_iterations < cold.num_iterations);
+    assert!((warm.best_solution[0] - 1.01).abs() < 1.0E-4);
+}
+
+#[test]
+fn multipliers_and_barrier_parameter_are_taken_over() {
+    let nlp = NlpBuilder::new()
+        .objective(|xs| xs[0].powi(2) + xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * xs[0], 2.0 * xs[1]])
+        .initial_guess(vec![1.0, 1.0])
+        .equality_constraint(|xs| xs[0] + xs[1] - 0.5, |_| vec![1.0, 1.0])
+        .inequality_constraint(|xs| xs[0] - 2.0, |_| vec![1.0, 0.0])
+        .build()
+        .unwrap();
+
+    let warm_start = WarmStart {
+        x: vec![0.25, 0.25],
+        inequality_multipliers: vec![0.0],
+        equality_multipliers: vec![-0.5],
+        barrier_parameter: Some(1.0E-9),
+        inverse_hessian: Some(vec![0.5, 0.0, 0.0, 0.5]),
+    };
+
+    let solution = Solver::new(
+        &nlp,
+        Options {
+            termination: OptionsTermination {
+                max_iterations: 0,
+                ..Default::default()
+            },
+            ..quiet()
+        },
+    )
+    .with_warm_start(warm_start.clone())
+    .solve();
+
+    // the warm start is optimal already, which the solver sees before the iteration limit
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert_eq!(solution.num_iterations, 0);
+    assert_eq!(solution.best_solution, warm_start.x);
+    assert_eq!(solution.multipliers.equality, [-0.5]);
+    assert_eq!(solution.barrier_parameter, 1.0E-9);
+    assert_eq!(solution.inverse_hessian, warm_start.inverse_hessian);
+}
+
+#[test]
+fn warm_start_of_the_wrong_size_is_rejected() {
+    let nlp = rosenbrock(1.0);
+    let warm_start = WarmStart {
+        x: vec![0.5, 0.5],
+        inequality_multipliers: vec![],
+        equality_multipliers: vec![],
+        barrier_parameter: None,
+        inverse_hessian: Some(vec![1.0, 0.0, 0.0]),
+    };
+
+    let solution = Solver::new(&nlp, quiet())
+        .with_warm_start(warm_start.clone())
+        .solve();
+
+    assert_eq!(
+        solution.status,
+        TerminationStatus::InvalidNlp(NlpValidationError::WarmStartLengthMismatch {
+            field: "inverse_hessian",
+            expected: 4,
+            actual: 3
+        })
+    );
+
+    let solution = Solver::new(&nlp, quiet())
+        .with_warm_start(WarmStart {
+            inequality_multipliers: vec![0.0],
+            ..warm_start
+        })
+        .solve();
+
+    assert_eq!(
+        solution.status,
+        TerminationStatus::InvalidNlp(NlpValidationError::WarmStartLengthMismatch {
+            field: "inequality_multipliers",
+            expected: 0,
+            actual: 1
+        })
+    );
+    assert_eq!(solution.num_iterations, 0);
+}
+
+#[test]
+fn warm_start_from_an_optimal_solution_converges_immediately() {
+    // min (x0 - 2)^2 + x1^2 s.t. x0 <= 1, optimal at (1, 0) with multiplier 2
+    let nlp = NlpBuilder::new()
+        .objective(|xs| (xs[0] - 2.0).powi(2) + xs[1].powi(2))
+        .grad_objective(|xs| vec![2.0 * (xs[0] - 2.0), 2.0 * xs[1]])
+        .initial_guess(vec![0.0, 1.0])
+        .inequality_constraint(|xs| xs[0] - 1.0, |_| vec![1.0, 0.0])
+        .build()
+        .unwrap();
+
+    let warm_start = WarmStart {
+        x: vec![1.0, 0.0],
+        inequality_multipliers: vec![2.0],
+        equality_multipliers: vec![],
+        barrier_parameter: None,
+        inverse_hessian: None,
+    };
+
+    let solution = Solver::new(&nlp, quiet())
+        .with_warm_start(warm_start.clone())
+        .solve();
+
+    assert_eq!(solution.status, TerminationStatus::Converged);
+    assert_eq!(solution.num_iterations, 0);
+    assert_eq!(solution.best_solution, warm_start.x);
+    assert_eq!(solution.statistics.evaluations.objective, 1);
+    assert_eq!(solution.optimality.lagrangian_gradient_norm, 0.0);
+    assert_eq!(solution.optimality.complementarity, 0.0);
+}